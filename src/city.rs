@@ -4,7 +4,6 @@ pub mod institutions;
 pub mod locations;
 pub mod population;
 pub mod city {
-    use std::collections::HashMap;
     use std::fs::File;
     use std::io::Write;
     use std::time::Instant;
@@ -17,19 +16,46 @@ pub mod city {
     use super::population::mind::mind::*;
     use super::population::mind::relations::relations::*;
     use crate::city::institutions::institutions::*;
-    use crate::city::institutions::visits::visits::{run_citizen_shopping, run_citizen_social};
+    use crate::city::institutions::justice::justice::{
+        print_crime_statistics, run_justice_per_year, Crime,
+    };
+    use crate::city::institutions::visits::visits::{
+        run_citizen_shopping, run_citizen_social, run_citizen_worship,
+    };
+    use crate::city::institutions::worship::worship::{
+        found_places_of_worship_per_year, link_priests_per_year, ordain_priests_per_year,
+    };
     use crate::city::locations::{locations, locations::*};
+    use crate::city::population::mind::appearance::appearance::age_appearance_per_year;
+    use crate::city::population::mind::faith::faith::update_faith_per_year;
+    use crate::city::population::mind::naming::naming::{
+        get_full_name, inherit_titles, update_names_per_year,
+    };
+    use crate::city::population::mind::relations::adoption::adoption::{
+        adopt_foundlings_per_year, adopt_orphans_per_year,
+    };
     use crate::city::population::mind::relations::friends::friends::*;
+    use crate::city::population::mind::relations::grudges::grudges::{
+        adventure_hooks, escalate_feuds_per_year, Feud,
+    };
+    use crate::city::population::mind::relations::marriage::marriage::arrange_marriages_per_year;
     use crate::city::population::mind::relations::parents::parents::*;
     use crate::city::population::mind::relations::partners::partners::*;
     use crate::city::population::mind::relations::residences::residences::{
-        assign_residences, random_evictions,
+        assign_residences, print_housing_censuses, record_housing_census, relocate_households,
+        HousingCensus,
+    };
+    use crate::city::population::mind::relations::housing_market::housing_market::{
+        link_tenancies, print_housing_statistics, run_housing_market_per_year,
     };
+    use crate::city::population::mind::relations::widowhood::widowhood::bereave_survivors;
+    use crate::city::population::mind::retirement::retirement::retire_per_year;
+    use crate::city::population::household::household::{update_households_per_year, Household};
     use crate::city::population::population::*;
     use crate::culture::culture::*;
+    use crate::culture::fertility::fertility::child_death_chance;
     use crate::language::language::*;
-
-    const MAX_WORKING_AGE: u32 = 60;
+    use crate::utils::utils::{thread_rng, HashMap};
 
     #[derive(PartialEq, Debug, Clone)]
     pub struct City {
@@ -40,6 +66,10 @@ pub mod city {
         pub buildings: HashMap<Uuid, Building>,
         pub building_floors: HashMap<Uuid, BuildingFloor>,
         pub building_floor_areas: HashMap<Uuid, BuildingFloorArea>,
+        pub households: HashMap<Uuid, Household>,
+        pub crimes: HashMap<Uuid, Crime>,
+        pub feuds: HashMap<Uuid, Feud>,
+        pub housing_censuses: Vec<HousingCensus>,
         pub culture: CultureConfig,
         pub year: usize,
     }
@@ -62,10 +92,30 @@ pub mod city {
                 .count() as f32
                 / adult_population as f32
         ));
+        output.push_str(&format!("Households: {}\n", city.households.len()));
         output.push_str(&format!(
             "Dead: {}\n",
             city.citizens.iter().filter(|(_id, c)| !c.alive).count()
         ));
+        output.push_str(&print_population_pyramid(&city));
+        output.push_str("Faiths:\n");
+        for faith in city.culture.faiths.iter() {
+            output.push_str(&format!(
+                "  {}: {} followers\n",
+                faith.name,
+                city.citizens
+                    .values()
+                    .filter(|c| c.alive && c.faith.eq(&Some(faith.id)))
+                    .count()
+            ));
+        }
+        output.push_str(&print_crime_statistics(&city));
+        output.push_str(&print_housing_statistics(&city));
+        output.push_str(&print_housing_censuses(&city));
+        output.push_str("Adventure Hooks:\n");
+        for hook in adventure_hooks(&city) {
+            output.push_str(&format!("  {}\n", hook));
+        }
         for a in city.areas.values() {
             output.push_str(&print_location(&a, &city));
         }
@@ -84,9 +134,8 @@ pub mod city {
         let mut output = String::new();
         for citizen in city.citizens.values() {
             output.push_str(&format!(
-                "==== {} {} - {} ====\n",
-                citizen.first_name,
-                citizen.last_name,
+                "==== {} - {} ====\n",
+                get_full_name(&citizen),
                 citizen.gender.to_string()
             ));
             for line in citizen.activity_log.iter() {
@@ -153,7 +202,7 @@ pub mod city {
         institution: Institution,
         dict: &Vec<Word>,
     ) -> &'a mut City {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let employee_count = ((rng.gen::<f32>() * 10.0) as i32).max(1);
         let all_workers = find_workers(&city);
         let workers = all_workers.iter().take(employee_count as usize);
@@ -175,16 +224,16 @@ pub mod city {
     fn find_workers<'a>(city: &'a City) -> Vec<Mind> {
         let mut output: Vec<Mind> = Vec::new();
         for mind in city.citizens.values() {
-            if mind.age < MAX_WORKING_AGE && mind.employer.is_none() {
+            if mind.age < city.culture.retirement_age && mind.employer.is_none() {
                 output.push(mind.clone());
             }
         }
-        output.shuffle(&mut rand::thread_rng());
+        output.shuffle(&mut thread_rng());
         return output;
     }
 
     pub fn old_age_pass_per_year<'a>(city: &'a mut City, culture: &CultureConfig) -> &'a mut City {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let citizen_ids: Vec<Uuid> = city
             .citizens
             .values()
@@ -195,11 +244,12 @@ pub mod city {
         let mut dead_ids: Vec<Uuid> = vec![];
         for mind_id in citizen_ids {
             let mind = city.citizens.get_mut(&mind_id).unwrap();
-            let death_odds = base_death_chance
+            let old_age_odds = base_death_chance
                 + (((mind.age as f32 - culture.species_avg_lifespan_variance as f32)
                     - (culture.species_avg_lifespan as f32
                         - culture.species_avg_lifespan_variance as f32))
                     / 10.0);
+            let death_odds = old_age_odds.max(child_death_chance(mind.age, &culture.fertility));
             if rng.gen::<f32>() < death_odds {
                 mind.activity_log
                     .push(format!("Died in year {} age {}", city.year, mind.age));
@@ -219,23 +269,12 @@ pub mod city {
             for (verb, id) in mind.relations.clone() {
                 if dead_ids.contains(&id) {
                     match verb {
-                        RelationVerb::Partner => {
-                            mind.relations
-                                .retain(|(v, id)| !(id.eq(&mind.id) && v.eq(&verb)));
-                            mind.relations.push((RelationVerb::LatePartner, id.clone()));
-                        }
-                        RelationVerb::Spouse => {
-                            mind.relations
-                                .retain(|(v, id)| !(id.eq(&mind.id) && v.eq(&verb)));
-                            mind.relations.push((RelationVerb::LateSpouse, id.clone()));
-                        }
-
                         RelationVerb::Acquaintance
                         | RelationVerb::Friend
                         | RelationVerb::CloseFriend
                         | RelationVerb::Colleague => {
                             mind.relations
-                                .retain(|(v, id)| !(id.eq(&mind.id) && social_verbs.contains(&v)));
+                                .retain(|(v, rid)| !(rid.eq(&id) && social_verbs.contains(&v)));
                         }
 
                         _ => {}
@@ -243,6 +282,8 @@ pub mod city {
                 }
             }
         }
+        bereave_survivors(city, &dead_ids);
+        inherit_titles(city, &dead_ids);
         return city;
     }
 
@@ -261,23 +302,32 @@ pub mod city {
         };
     }
 
+    // A city with no people or buildings yet
+    pub fn new_city(name: String, culture: &CultureConfig) -> City {
+        return City {
+            name,
+            buildings: HashMap::default(),
+            building_floors: HashMap::default(),
+            building_floor_areas: HashMap::default(),
+            citizens: HashMap::default(),
+            areas: HashMap::default(),
+            institutions: HashMap::default(),
+            households: HashMap::default(),
+            crimes: HashMap::default(),
+            feuds: HashMap::default(),
+            housing_censuses: Vec::new(),
+            culture: culture.clone(),
+            year: 0,
+        };
+    }
+
     pub fn simulate(size: usize, age: usize, era: Option<Era>) -> City {
         let dict = build_dictionary();
         let culture = random_culture(&dict, &era);
 
         println!("{:#?}", culture);
         let dict = build_culture_dictionary(&dict, &culture);
-        let mut city = City {
-            name: locations::gen_location_name(&dict, false, &era),
-            buildings: HashMap::new(),
-            building_floors: HashMap::new(),
-            building_floor_areas: HashMap::new(),
-            citizens: HashMap::new(),
-            areas: HashMap::new(),
-            institutions: HashMap::new(),
-            culture: culture.clone(),
-            year: 0,
-        };
+        let mut city = new_city(locations::gen_location_name(&dict, false, &era), &culture);
         generate_population_baseline(&dict, size, &mut city);
         let public_institutions = generate_public_institutions(&dict, &era);
 
@@ -299,7 +349,14 @@ pub mod city {
             let old_age_benchmarker = create_benchmarker(String::from("Old Age"));
             old_age_pass_per_year(&mut city, &culture);
             old_age_benchmarker();
-            // Very Slow
+
+            let appearance_benchmarker = create_benchmarker(String::from("Appearance"));
+            age_appearance_per_year(&mut city, &dict);
+            appearance_benchmarker();
+
+            let names_benchmarker = create_benchmarker(String::from("Names"));
+            update_names_per_year(&mut city, &dict);
+            names_benchmarker();
             let friend_linking_benchmarker = create_benchmarker(String::from("Link Friends"));
             link_friends_within_population_by_year(&mut city);
             friend_linking_benchmarker();
@@ -308,23 +365,52 @@ pub mod city {
             link_partners_by_year(&mut city);
             partner_linking_benchmarker();
 
+            let arranged_marriage_benchmarker =
+                create_benchmarker(String::from("Arrange Marriages"));
+            arrange_marriages_per_year(&mut city);
+            arranged_marriage_benchmarker();
+
             let partner_update_benchmarker = create_benchmarker(String::from("Update Partners"));
             update_partners_by_year(&mut city);
             partner_update_benchmarker();
 
+            let feuds_benchmarker = create_benchmarker(String::from("Feuds"));
+            escalate_feuds_per_year(&mut city);
+            feuds_benchmarker();
+
             let generate_children_benchmarker =
                 create_benchmarker(String::from("Generate Children"));
             generate_children_per_year(&mut city, &culture, &dict);
             generate_children_benchmarker();
 
+            let adoption_benchmarker = create_benchmarker(String::from("Adoption"));
+            adopt_orphans_per_year(&mut city);
+            adopt_foundlings_per_year(&mut city, &dict);
+            adoption_benchmarker();
+
             let add_buildings_benchmarker = create_benchmarker(String::from("Add Buildings"));
             add_buildings_per_year(&mut city, &dict);
             add_buildings_benchmarker();
 
-            let eviction_benchmarker = create_benchmarker(String::from("Evictions"));
-            random_evictions(&mut city);
+            let crime_benchmarker = create_benchmarker(String::from("Crime"));
+            run_justice_per_year(&mut city);
+            crime_benchmarker();
+
+            let households_benchmarker = create_benchmarker(String::from("Households"));
+            update_households_per_year(&mut city);
+            households_benchmarker();
+
+            let housing_benchmarker = create_benchmarker(String::from("Housing"));
+            run_housing_market_per_year(&mut city);
+            relocate_households(&mut city);
             assign_residences(&mut city);
-            eviction_benchmarker();
+            link_tenancies(&mut city);
+            record_housing_census(&mut city);
+            housing_benchmarker();
+
+            let retirement_benchmarker = create_benchmarker(String::from("Retirement"));
+            retire_per_year(&mut city);
+            retirement_benchmarker();
 
             let sackings_benchmarker = create_benchmarker(String::from("Sackings"));
             random_sackings_per_year(&mut city);
@@ -347,6 +433,14 @@ pub mod city {
             run_citizen_social(&mut city);
             create_social_benchmarker();
 
+            let religion_benchmarker = create_benchmarker(String::from("Religion"));
+            update_faith_per_year(&mut city);
+            found_places_of_worship_per_year(&mut city, &dict);
+            ordain_priests_per_year(&mut city);
+            run_citizen_worship(&mut city);
+            link_priests_per_year(&mut city);
+            religion_benchmarker();
+
             for citizen in city.citizens.values_mut().filter(|c| c.alive) {
                 citizen.age += 1;
             }
@@ -1,6 +1,4 @@
 pub mod building {
-    use std::collections::HashSet;
-
     use rand::Rng;
     use uuid::Uuid;
 
@@ -8,13 +6,19 @@ pub mod building {
         city::city::*,
         city::{
             locations::locations::{gen_location, Location},
-            population::mind::mind::*,
+            population::mind::{
+                mind::*,
+                naming::naming::get_full_name,
+                relations::residences::residences::{count_vacant_homes, housing_demand},
+            },
         },
         culture::culture::*,
         language::language::*,
         templater::templater::*,
-        utils::utils::random_pick,
+        utils::utils::{random_pick, random_uuid, thread_rng, HashSet},
     };
+    const MAX_AREA_ROOMS: u32 = 4;
+
     #[derive(PartialEq, Debug, Clone)]
     pub enum FloorAreaType {
         Apartment,  // anywhere bar ground floor
@@ -25,6 +29,11 @@ pub mod building {
         Lobby,      // must have one, must be on ground floor
     }
     #[derive(PartialEq, Debug, Clone)]
+    pub enum Landlord {
+        Citizen(Uuid),
+        Institution(Uuid),
+    }
+    #[derive(PartialEq, Debug, Clone)]
     pub enum FloorType {
         Residential,
         Commercial,
@@ -36,6 +45,10 @@ pub mod building {
         pub area_type: FloorAreaType,
         pub owning_institution: Option<Uuid>,
         pub floor_id: Uuid,
+        pub rooms: u32,
+        // Yearly rent asked of tenants, set by the housing market
+        pub rent: i32,
+        pub landlord: Option<Landlord>,
     }
     #[derive(PartialEq, Debug, Clone)]
     pub struct BuildingFloor {
@@ -89,7 +102,7 @@ pub mod building {
                     } else {
                         let mut names: Vec<String> = Vec::new();
                         for resident in residents {
-                            names.push(format!("{} {}", resident.first_name, resident.last_name));
+                            names.push(get_full_name(&resident));
                         }
                         output.push_str(&format!(
                             "          {}: {}\n",
@@ -111,44 +124,57 @@ pub mod building {
     }
 
     fn new_floor(level: i32, floor_type: FloorType, building_id: &Uuid, culture: &CultureConfig) -> BuildingFloor {
-        let floor_id = Uuid::new_v4();
-        let mut rng = rand::thread_rng();
+        let floor_id = random_uuid();
+        let mut rng = thread_rng();
         let mut areas: Vec<BuildingFloorArea> = Vec::new();
         let footprint = culture.avg_building_footprint / 2;
         let area_count = footprint + (rng.gen::<f32>() * footprint as f32) as i32;
         for i in 0..=area_count {
+            let rooms = 1 + (rng.gen::<f32>() * MAX_AREA_ROOMS as f32) as u32;
             if level == 0 && i == 0 {
                 areas.push(BuildingFloorArea {
-                    id: Uuid::new_v4(),
+                    id: random_uuid(),
                     name: format!("{}{:0>2}", level, i + 1),
                     area_type: FloorAreaType::Lobby,
                     owning_institution: None,
-                    floor_id
+                    floor_id,
+                    rooms,
+                    rent: 0,
+                    landlord: None
                 });
             } else if level.eq(&(-1)) && i == 0 {
                 areas.push(BuildingFloorArea {
-                    id: Uuid::new_v4(),
+                    id: random_uuid(),
                     name: format!("{}{:0>2}", level, i + 1),
                     area_type: FloorAreaType::Utilities,
                     owning_institution: None,
-                    floor_id
+                    floor_id,
+                    rooms,
+                    rent: 0,
+                    landlord: None
                 });
             } else {
                 if floor_type.eq(&FloorType::Residential) {
                     areas.push(BuildingFloorArea {
-                        id: Uuid::new_v4(),
+                        id: random_uuid(),
                         name: format!("{}{:0>2}", level, i + 1),
                         area_type: FloorAreaType::Apartment,
                         owning_institution: None,
-                        floor_id
+                        floor_id,
+                        rooms,
+                        rent: 0,
+                        landlord: None
                     });
                 } else {
                     areas.push(BuildingFloorArea {
-                        id: Uuid::new_v4(),
+                        id: random_uuid(),
                         name: format!("{}{:0>2}", level, i + 1),
                         area_type: FloorAreaType::Commercial,
                         owning_institution: None,
-                        floor_id
+                        floor_id,
+                        rooms,
+                        rent: 0,
+                        landlord: None
                     });
                 }
             }
@@ -158,7 +184,7 @@ pub mod building {
             level,
             floor_type,
             areas,
-            area_ids: HashSet::new(),
+            area_ids: HashSet::default(),
             building_id: building_id.clone()
         };
     }
@@ -177,13 +203,13 @@ pub mod building {
         culture: &CultureConfig,
         residential: bool,
     ) -> Building {
-        let id = Uuid::new_v4();
+        let id = random_uuid();
         let name_templates = vec![
             "{{{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName)}} {{Noun(BuildingTitle)}}",
             "{{Noun(LastName)}} {{Noun(BuildingTitle)}}",
             "{{{{Adjective(Position, Quality, Age, Colour)}} {{Noun(BuildingTitle)}}",
         ];
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut floors: Vec<BuildingFloor> = Vec::new();
         let floor_count =
             ((rng.gen::<f32>() * ((culture.avg_building_floors * 2) + 1) as f32) as i32).max(2);
@@ -207,7 +233,7 @@ pub mod building {
             id,
             name: render_template_2(random_pick(&name_templates), &dict, &culture.era),
             floors,
-            floor_ids: HashSet::new(),
+            floor_ids: HashSet::default(),
             location_id,
         };
     }
@@ -245,7 +271,7 @@ pub mod building {
     }
 
     pub fn add_building_to_city_2<'a>(city: &'a mut City, dict: &Vec<Word>, residential: bool) -> (&'a mut City, Uuid) {
-        let building_id = Uuid::new_v4();
+        let building_id = random_uuid();
         // Building Gen
         let name_templates = vec![
             "{{{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName)}} {{Noun(BuildingTitle)}}",
@@ -265,12 +291,12 @@ pub mod building {
             id: building_id,
             name,
             floors: Vec::new(),
-            floor_ids: HashSet::new(),
+            floor_ids: HashSet::default(),
             location_id: Some(free_location.unwrap().id),
         };
 
         // Floor Gen
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let floor_count =
             ((rng.gen::<f32>() * ((&city.culture.avg_building_floors * 2) + 1) as f32) as i32).max(2);
         let commercial_floor_count = if residential {
@@ -290,6 +316,7 @@ pub mod building {
             let floor = new_floor(i, floor_type, &building_id, &city.culture);
             
             building.floor_ids.insert(floor.id.clone());
+            building.floors.push(floor.clone());
             city.building_floors.insert(floor.id.clone(), floor);
         }
 
@@ -300,26 +327,15 @@ pub mod building {
         return (city, building_id);
     }
 
-    fn count_available_apartments(city: &City) -> usize {
-        return city
-            .buildings
-            .values()
-            .flat_map(|b| {
-                b.floors
-                    .iter()
-                    .filter(|f| f.floor_type.eq(&FloorType::Residential))
-                    .flat_map(|f| f.areas.clone())
-            })
-            .count();
-    }
-
+    // Build until there are enough empty homes for the households that are homeless or overcrowded
     pub fn add_buildings_per_year<'a>(city: &'a mut City, dict: &Vec<Word>) -> &'a mut City {
-        let living_citizen_count = city.citizens.values().filter(|c| c.alive).count();
-        let acceptable_homeless_count = living_citizen_count / 100;
-        let mut free_apartment_count = count_available_apartments(&city);
-        while free_apartment_count < (living_citizen_count - acceptable_homeless_count) {
+        let living_household_count = city.households.len();
+        let acceptable_shortfall = living_household_count / 100;
+        let demand = housing_demand(&city);
+        let mut vacant_home_count = count_vacant_homes(&city);
+        while vacant_home_count + acceptable_shortfall < demand {
             add_building_to_city_2(city, &dict, true);
-            free_apartment_count = count_available_apartments(&city);
+            vacant_home_count = count_vacant_homes(&city);
         }
         return city;
     }
@@ -7,10 +7,21 @@ pub mod html_exporter {
         building::building::Building,
         city::City,
         institutions::institutions::find_institution_address,
+        institutions::justice::justice::print_crime_statistics,
         locations::locations::Location,
         population::mind::{
+            appearance::appearance::describe_appearance,
+            faith::faith::describe_faith,
             mind::{find_address, get_name_from_id, Mind, find_employer},
-            relations::{relations::RelationVerb, friends::friends::SOCIAL_RELATIONS},
+            naming::naming::get_full_name,
+            relations::{
+                relations::RelationVerb,
+                friends::friends::SOCIAL_RELATIONS,
+                grudges::grudges::{adventure_hooks, describe_grudge_cause},
+                housing_market::housing_market::print_housing_statistics,
+                kinship::kinship::{describe_kinship, kinship},
+                residences::residences::{describe_housing_need, print_housing_censuses},
+            },
         },
     };
 
@@ -29,6 +40,23 @@ pub mod html_exporter {
         writeln!(body.p(), "Dead: {}", dead.clone().count()).unwrap();
         writeln!(body.p(), "Area Count: {}", city.areas.len()).unwrap();
         writeln!(body.p(), "Building Count: {}", city.buildings.len()).unwrap();
+        for line in print_crime_statistics(&city).lines() {
+            writeln!(body.p(), "{}", line.trim()).unwrap();
+        }
+        for line in print_housing_statistics(&city).lines() {
+            writeln!(body.p(), "{}", line.trim()).unwrap();
+        }
+        for line in print_housing_censuses(&city).lines() {
+            writeln!(body.p(), "{}", line.trim()).unwrap();
+        }
+        let hooks = adventure_hooks(&city);
+        if hooks.len() > 0 {
+            writeln!(body.h2(), "Adventure Hooks:").unwrap();
+            let mut hook_list = body.ul();
+            for hook in hooks {
+                writeln!(hook_list.li(), "{}", hook).unwrap();
+            }
+        }
         writeln!(body.h2(), "Locations:").unwrap();
         let mut loc_list = body.ul();
         for area in city.areas.values() {
@@ -110,9 +138,8 @@ pub mod html_exporter {
                                 "href='#{}' class=\"floor_resident_nametag\"",
                                 resident.id
                             )),
-                            "{} {}",
-                            resident.first_name,
-                            resident.last_name
+                            "{}",
+                            get_full_name(&resident)
                         )
                         .unwrap();
                     }
@@ -121,6 +148,55 @@ pub mod html_exporter {
                 }
             }
         }
+        // How the people living here know one another
+        let building_areas: Vec<Uuid> = building
+            .floors
+            .iter()
+            .flat_map(|f| f.areas.iter().map(|a| a.id.clone()))
+            .collect();
+        let residents: Vec<&Mind> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.residence.is_some_and(|r| building_areas.contains(&r)))
+            .collect();
+        let resident_ids: Vec<Uuid> = residents.iter().map(|m| m.id.clone()).collect();
+        let network: Vec<(&Mind, Vec<&(RelationVerb, Uuid)>)> = residents
+            .iter()
+            .map(|m| {
+                (
+                    *m,
+                    m.relations
+                        .iter()
+                        .filter(|(_v, id)| resident_ids.contains(id))
+                        .collect::<Vec<&(RelationVerb, Uuid)>>(),
+                )
+            })
+            .filter(|(_m, ties)| ties.len() > 0)
+            .collect();
+        if network.len() > 0 {
+            writeln!(base.h6(), "Resident Network").unwrap();
+            let mut network_list = base.ul();
+            for (resident, ties) in network {
+                let mut item = network_list.li();
+                let mut para = item.p();
+                writeln!(
+                    para.a().attr(&format!("href='#{}'", resident.id)),
+                    "{}",
+                    get_full_name(&resident)
+                )
+                .unwrap();
+                writeln!(para, ":").unwrap();
+                for (verb, id) in ties {
+                    writeln!(para, "{:?}", verb).unwrap();
+                    writeln!(
+                        para.a().attr(&format!("href='#{}'", id)),
+                        "{}",
+                        get_name_from_id(&id, &city.citizens)
+                    )
+                    .unwrap();
+                }
+            }
+        }
         return node;
     }
 
@@ -154,25 +230,20 @@ pub mod html_exporter {
         let workplace = find_employer(&mind, &city);
 
         let mut list_element = node.div().attr(&format!("id='{}'", mind.id));
+        writeln!(list_element.h3(), "Name: {}", get_full_name(&mind)).unwrap();
+        writeln!(list_element.p(), "Gender: {}", &mind.gender).unwrap();
+        writeln!(list_element.p(), "Age: {}", &mind.age).unwrap();
         writeln!(
-            list_element.h3(),
-            "Name: {} {}",
-            &mind.first_name,
-            &mind.last_name
+            list_element.p(),
+            "Description: {}\n",
+            describe_appearance(&mind.physical_description)
         )
         .unwrap();
-        writeln!(list_element.p(), "Gender: {}", &mind.gender).unwrap();
-        writeln!(list_element.p(), "Age: {}", &mind.age).unwrap();
-        let description = &mind.physical_description;
+
         writeln!(
             list_element.p(),
-            "Description: has {}, {} {} hair and {} eyes. Is {} with a {} build.\n",
-            description.hair_adjectives.first().unwrap(),
-            description.hair_length,
-            description.hair_colour,
-            description.eye_colour,
-            description.height_adjective,
-            description.build_adjective
+            "Faith: {}",
+            describe_faith(&mind, &city.culture)
         )
         .unwrap();
 
@@ -194,6 +265,8 @@ pub mod html_exporter {
                 location.name
             )
             .unwrap();
+        } else if mind.retired {
+            writeln!(list_element.p(), "Employer: Retired").unwrap();
         } else {
             writeln!(list_element.p(), "Employer: None").unwrap();
         }
@@ -223,6 +296,10 @@ pub mod html_exporter {
             )
             .unwrap();
         }
+        let housing_need = describe_housing_need(&mind, &city);
+        if housing_need.is_some() {
+            writeln!(list_element.p(), "Housing: {}", housing_need.unwrap()).unwrap();
+        }
         let mut relations: Vec<(&RelationVerb, String, Uuid)> = mind
             .relations
             .iter()
@@ -231,11 +308,11 @@ pub mod html_exporter {
         relations.sort_by_key(|a| a.0.to_string());
 
         let mut social_relations= relations.clone();
-        social_relations.retain(|(verb, _, _)| SOCIAL_RELATIONS.contains(&verb));
+        social_relations.retain(|(verb, _, _)| SOCIAL_RELATIONS.contains(&verb) || verb.eq(&&RelationVerb::Grudge) || verb.eq(&&RelationVerb::Neighbour) || verb.eq(&&RelationVerb::BuildingNeighbour));
         let mut immediate_relations = relations.clone();
-        immediate_relations.retain(|(verb, _, _)| vec![RelationVerb::Parent, RelationVerb::Child, RelationVerb::Partner, RelationVerb::Spouse, RelationVerb::ExPartner, RelationVerb::ExSpouse, RelationVerb::Sibling].contains(&verb));
+        immediate_relations.retain(|(verb, _, _)| vec![RelationVerb::Parent, RelationVerb::Child, RelationVerb::AdoptedParent, RelationVerb::AdoptedChild, RelationVerb::Partner, RelationVerb::Spouse, RelationVerb::ExPartner, RelationVerb::ExSpouse, RelationVerb::Sibling, RelationVerb::StepParent, RelationVerb::StepChild, RelationVerb::StepSibling].contains(&verb));
         let mut extended_relations = relations.clone();
-        extended_relations.retain(|(verb, _, _)| vec![RelationVerb::Grandchild, RelationVerb::Grandparent, RelationVerb::Pibling, RelationVerb::Nibling].contains(&verb));
+        extended_relations.retain(|(verb, _, _)| vec![RelationVerb::Grandchild, RelationVerb::Grandparent, RelationVerb::Pibling, RelationVerb::Nibling, RelationVerb::ParentInLaw, RelationVerb::ChildInLaw, RelationVerb::SiblingInLaw].contains(&verb));
 
         if immediate_relations.len() < 1 {
             writeln!(list_element.p(), "Immediate Relations: None").unwrap();
@@ -252,6 +329,16 @@ pub mod html_exporter {
                     name
                 )
                 .unwrap();
+                let relative = city.citizens.get(&id);
+                if relative.is_some() {
+                    let term = kinship(relative.unwrap(), mind, &city.citizens);
+                    if term.is_some() {
+                        writeln!(list_el_para, "({})", describe_kinship(&term.unwrap(), &relative.unwrap().gender)).unwrap();
+                    }
+                }
+                if mind.custody.contains_key(&id) {
+                    writeln!(list_el_para, "(Custody: {})", mind.custody.get(&id).unwrap()).unwrap();
+                }
             }
         }
         if social_relations.len() < 1 {
@@ -269,6 +356,12 @@ pub mod html_exporter {
                     name
                 )
                 .unwrap();
+                if verb.eq(&RelationVerb::Grudge) && mind.grudge_causes.contains_key(&id) {
+                    writeln!(list_el_para, "over {}", describe_grudge_cause(mind.grudge_causes.get(&id).unwrap())).unwrap();
+                }
+                if mind.affinities.contains_key(&id) {
+                    writeln!(list_el_para, "(Affinity: {})", mind.affinities.get(&id).unwrap()).unwrap();
+                }
             }
         }
         if extended_relations.len() < 1 {
@@ -286,6 +379,13 @@ pub mod html_exporter {
                     name
                 )
                 .unwrap();
+                let relative = city.citizens.get(&id);
+                if relative.is_some() {
+                    let term = kinship(relative.unwrap(), mind, &city.citizens);
+                    if term.is_some() {
+                        writeln!(list_el_para, "({})", describe_kinship(&term.unwrap(), &relative.unwrap().gender)).unwrap();
+                    }
+                }
             }
         }
         
@@ -1,22 +1,28 @@
 pub mod food_institutions;
+pub mod justice;
 pub mod visits;
+pub mod worship;
 pub mod institutions {
     use rand::seq::SliceRandom;
     use rand::Rng;
     use uuid::Uuid;
 
     use crate::city::building::building::{Building, BuildingFloor, BuildingFloorArea};
+    use crate::city::institutions::justice::justice::is_imprisoned;
     use crate::city::city::{add_institution_to_city, City};
     use crate::city::locations::locations::Location;
     use crate::city::population::mind::mind::{
         add_leaving_workplace_to_mind_log, add_new_workplace_to_mind_log,
         add_startup_creation_to_mind_log, Mind,
     };
+    use crate::city::population::mind::relations::grudges::grudges::{
+        blame_for_sacking, start_business_rivalry,
+    };
     use crate::city::population::mind::relations::relations::ADULT_AGE_FROM;
     use crate::culture::culture::CultureConfig;
     use crate::language::language::*;
     use crate::templater::templater::*;
-    use crate::utils::utils::random_pick;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng};
 
     use super::food_institutions::food_institutions::{
         random_general_food_outlet, random_specialist_food_outlet,
@@ -44,6 +50,8 @@ pub mod institutions {
         EntertainmentVenue,      // Thearters, cinemas, nightclubs
         IndustrialManufacturers, // Goods manufacturers
         AdministrationService,
+        // Religious
+        PlaceOfWorship,
     }
 
     #[derive(PartialEq, Debug, Clone)]
@@ -115,6 +123,8 @@ pub mod institutions {
         pub customer_cost_multipler: f32,
         pub wealth: usize,
         pub annual_visits: usize,
+        // The faith served by a place of worship
+        pub faith: Option<Uuid>,
     }
 
     const PUBLIC_INSTITUTES: [InstituteType; 11] = [
@@ -136,6 +146,8 @@ pub mod institutions {
 
     const RANDOM_SACKING_RATE: f32 = 0.1;
     const STARTUP_RATE: f32 = 0.01;
+    const SACKING_GRUDGE_CHANCE: f32 = 0.2;
+    const BUSINESS_RIVALRY_CHANCE: f32 = 0.3;
 
     fn label_insitute_type(i: &InstituteType, era: &Option<Era>) -> String {
         return String::from(match (i, era) {
@@ -220,11 +232,11 @@ pub mod institutions {
     }
 
     pub fn generate_public_institutions(dict: &Vec<Word>, era: &Option<Era>) -> Vec<Institution> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut output: Vec<Institution> = Vec::new();
         for i in PUBLIC_INSTITUTES {
             output.push(Institution {
-                id: Uuid::new_v4(),
+                id: random_uuid(),
                 name: format!(
                     "{} {}",
                     render_template_2("{{Noun(HistoricalFigure)}}", &dict, &era),
@@ -235,6 +247,7 @@ pub mod institutions {
                 size: (rng.gen::<f32>() * PUBLIC_INSTITUTE_BASE_SIZE as f32) as usize,
                 serves: Vec::new(),
                 customer_cost_multipler: rng.gen::<f32>() * 5.0,
+                faith: None,
                 annual_visits: 0,
                 wealth: 0,
             });
@@ -243,7 +256,7 @@ pub mod institutions {
     }
 
     pub fn generate_specialist_retailer(dict: &Vec<Word>, era: &Option<Era>) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let templates = vec![
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName)}} {{Noun(RetailerSpecialist)}}",
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName)}}'s {{Noun(RetailerSpecialist)}}",
@@ -252,20 +265,21 @@ pub mod institutions {
         ];
         let name = render_template_2(random_pick(&templates), &dict, era);
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name,
             public: false,
             institute_type: InstituteType::SpecialistRetail,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: Vec::new(),
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0,
         };
     }
 
     pub fn generate_general_retailer(dict: &Vec<Word>, era: &Option<Era>) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let templates = vec![
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}} {{Noun(GeneralRetailerName)}}",
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}}'s {{Noun(GeneralRetailerName)}}",
@@ -274,20 +288,21 @@ pub mod institutions {
         ];
         let name = render_template_2(random_pick(&templates), &dict, era);
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name,
             public: false,
             institute_type: InstituteType::GeneralRetail,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: Vec::new(),
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0,
         };
     }
 
     pub fn generate_admin(dict: &Vec<Word>, era: &Option<Era>) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let templates = vec![
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}} {{Noun(ServiceAdmin)}}",
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}}'s {{Noun(ServiceAdmin)}}",
@@ -296,20 +311,21 @@ pub mod institutions {
         ];
         let name = render_template_2(random_pick(&templates), &dict, era);
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name,
             public: false,
             institute_type: InstituteType::AdministrationService,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: Vec::new(),
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0,
         };
     }
 
     pub fn generate_entertainment(dict: &Vec<Word>, era: &Option<Era>) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let templates = vec![
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}} {{Noun(EntertainmentVenu)}}",
             "{{Adjective(Position, Quality, Age, Colour)}} {{Noun(LastName}}'s {{Noun(EntertainmentVenu)}}",
@@ -318,13 +334,14 @@ pub mod institutions {
         ];
         let name = render_template_2(random_pick(&templates), &dict, era);
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name,
             public: false,
             institute_type: InstituteType::EntertainmentVenue,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: Vec::new(),
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0,
         };
@@ -334,7 +351,7 @@ pub mod institutions {
         dict: &Vec<Word>,
         culture: &Option<CultureConfig>,
     ) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let era = if culture.is_some() {
             culture.clone().unwrap().era
         } else {
@@ -392,7 +409,8 @@ pub mod institutions {
     }
 
     pub fn random_sackings_per_year<'a>(city: &'a mut City) -> &'a mut City {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
+        let mut sacked: Vec<(Uuid, Uuid)> = Vec::new();
         let employed = city
             .citizens
             .values_mut()
@@ -400,11 +418,17 @@ pub mod institutions {
         for mind in employed {
             if rng.gen::<f32>() < RANDOM_SACKING_RATE {
                 let employer = city.institutions.get(&mind.employer.unwrap());
+                sacked.push((mind.id.clone(), employer.unwrap().id.clone()));
                 mind.employer = None;
 
                 add_leaving_workplace_to_mind_log(mind, city.year, &employer.unwrap().name);
             }
         }
+        for (mind_id, employer_id) in sacked {
+            if rng.gen::<f32>() < SACKING_GRUDGE_CHANCE {
+                blame_for_sacking(city, &mind_id, &employer_id);
+            }
+        }
         return city;
     }
 
@@ -413,7 +437,7 @@ pub mod institutions {
         let citizens_ref = city.citizens.clone();
         let (employed, unemployed) = citizens_ref
             .values()
-            .filter(|c| c.alive && c.age > ADULT_AGE_FROM)
+            .filter(|c| c.alive && c.age > ADULT_AGE_FROM && !c.retired && !is_imprisoned(c))
             .fold(
                 (vec![], vec![]),
                 |(employed, unemployed): (Vec<&Mind>, Vec<&Mind>), c| {
@@ -435,7 +459,10 @@ pub mod institutions {
                     .count();
                 return (i, employee_count);
             })
-            .filter(|(i, c)| c < &i.size)
+            // clergy are ordained from their own faithful rather than hired
+            .filter(|(i, c)| {
+                c < &i.size && !i.institute_type.eq(&InstituteType::PlaceOfWorship)
+            })
             .collect();
         for mind in unemployed {
             let possible_target = under_strength_institutions.pop();
@@ -451,18 +478,24 @@ pub mod institutions {
                     under_strength_institutions.push((inst, emp_count + 1));
                 }
             }
-            under_strength_institutions.shuffle(&mut rand::thread_rng());
+            under_strength_institutions.shuffle(&mut thread_rng());
         }
         return city;
     }
 
     pub fn create_startups_per_year<'a>(city: &'a mut City, dict: &Vec<Word>) -> &'a mut City {
         // let mut city = city;
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let citizen_ref = city.citizens.clone();
         let unemployed = citizen_ref
             .values()
-            .filter(|c| c.alive && c.age > ADULT_AGE_FROM && c.employer.is_none());
+            .filter(|c| {
+                c.alive
+                    && c.age > ADULT_AGE_FROM
+                    && c.employer.is_none()
+                    && !c.retired
+                    && !is_imprisoned(c)
+            });
         for m in unemployed {
             if rng.gen::<f32>() < STARTUP_RATE {
                 let new_inst = generate_population_institution(&dict, &Some(city.culture.clone()));
@@ -470,7 +503,11 @@ pub mod institutions {
                 mind.employer = Some(new_inst.id.clone());
                 add_startup_creation_to_mind_log(mind, city.year, &new_inst.name);
                 drop(mind);
+                let new_inst_id = new_inst.id.clone();
                 add_institution_to_city(city, new_inst, &dict);
+                if rng.gen::<f32>() < BUSINESS_RIVALRY_CHANCE {
+                    start_business_rivalry(city, &m.id, &new_inst_id);
+                }
             }
         }
         return city;
@@ -1,7 +1,6 @@
 pub mod food_institutions {
     
     use rand::Rng;
-    use uuid::Uuid;
 
     use crate::city::institutions::institutions::{Institution, InstituteType, PRIVATE_INSTITUTE_BASE_SIZE};
     use crate::culture::culture::*;
@@ -13,10 +12,10 @@ pub mod food_institutions {
     };
     use crate::language::nouns::nouns::NounTag;
     use crate::templater::templater::render_template_2;
-    use crate::utils::utils::random_pick;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng};
 
     pub fn random_specialist_food_outlet(dict: &Vec<Word>, culture: &Option<CultureConfig>) -> Institution {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let era = if culture.is_some() {
             culture.clone().unwrap().era
         } else {
@@ -56,20 +55,21 @@ pub mod food_institutions {
         ),];
         let templates: Vec<&str> = templates_pre.iter().map(|i| i.as_str()).collect();
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name: render_template_2(random_pick(&templates), &dict, &era),
             public: false,
             institute_type: InstituteType::SpecialistFoodService,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: menu,
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0
         }
     }
 
     pub fn random_general_food_outlet(dict: &Vec<Word>, culture: &Option<CultureConfig>) -> Institution  {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let era = if culture.is_some() {
             culture.clone().unwrap().era
         } else {
@@ -96,13 +96,14 @@ pub mod food_institutions {
         ];
         let name = format!("{} {}", render_template_2(random_pick(&templates), &dict, &era), inst_type.text);
         return Institution {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name,
             public: false,
             institute_type: InstituteType::FoodService,
             size: (rng.gen::<f32>() * PRIVATE_INSTITUTE_BASE_SIZE as f32) as usize,
             serves: menu,
             customer_cost_multipler: rng.gen::<f32>() * 5.0,
+            faith: None,
             annual_visits: 0,
             wealth: 0
         };
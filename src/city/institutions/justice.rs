@@ -0,0 +1,458 @@
+pub mod justice {
+    use rand::Rng;
+    use strum_macros::Display;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::institutions::{
+        find_institution_address, InstituteType, Institution,
+    };
+    use crate::city::institutions::visits::visits::{get_habitual_institutions, VisitType};
+    use crate::city::population::household::household::leave_household;
+    use crate::city::population::mind::appearance::appearance::{wealth_tier, WealthTier};
+    use crate::city::population::mind::mind::{add_event_to_mind_log, Mind};
+    use crate::city::population::mind::naming::naming::get_full_name;
+    use crate::city::population::mind::relations::grudges::grudges::{add_grudge, GrudgeCause};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::{
+        random_pick, random_uuid, random_weighted_pick, thread_rng, HashMap,
+    };
+
+    const BASE_OFFENCE_RATE: f32 = 0.02;
+    const POOR_OFFENCE_MULTIPLIER: f32 = 2.0;
+    const WEALTHY_OFFENCE_MULTIPLIER: f32 = 0.6;
+    const YOUTH_OFFENCE_AGE: u32 = 25;
+
+    const BASE_SOLVE_RATE: f32 = 0.2;
+    // Officers needed per citizen for the police to be fully staffed
+    const POLICE_PER_CAPITA: f32 = 0.005;
+    const POLICE_SOLVE_RATE: f32 = 0.3;
+    // Years an unsolved case stays open before it goes cold
+    const CASE_OPEN_YEARS: usize = 3;
+    const CONVICTION_RATE: f32 = 0.7;
+    const VICTIM_GRUDGE_CHANCE: f32 = 0.5;
+    // Without a prison, courts fine the offender's household instead, per year of the sentence
+    const FINE_PER_SENTENCE_YEAR: i32 = 15;
+
+    #[derive(PartialEq, Debug, Clone, Display)]
+    pub enum Offence {
+        Theft,
+        Fraud,
+        Assault,
+        Vandalism,
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub enum Verdict {
+        Pending,
+        Guilty(usize),
+        // non-custodial sentence handed down when the city has no prison
+        Fined(i32),
+        NotGuilty,
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Crime {
+        pub id: Uuid,
+        pub offence: Offence,
+        pub offender: Uuid,
+        pub victim: Option<Uuid>,
+        pub location: Option<Uuid>,
+        pub year: usize,
+        pub investigator: Option<Uuid>,
+        pub solved: bool,
+        pub verdict: Verdict,
+    }
+
+    fn sentence_range(offence: &Offence) -> (usize, usize) {
+        return match offence {
+            Offence::Vandalism => (1, 1),
+            Offence::Theft => (1, 3),
+            Offence::Fraud => (1, 5),
+            Offence::Assault => (2, 8),
+        };
+    }
+
+    pub fn is_imprisoned(mind: &Mind) -> bool {
+        return mind.imprisoned_until.is_some();
+    }
+
+    fn staff_of(city: &City, institute_type: InstituteType) -> Vec<Uuid> {
+        let institutions: Vec<&Uuid> = city
+            .institutions
+            .values()
+            .filter(|i| i.institute_type.eq(&institute_type))
+            .map(|i| &i.id)
+            .collect();
+        return city
+            .citizens
+            .values()
+            .filter(|m| {
+                m.alive && m.employer.is_some() && institutions.contains(&&m.employer.unwrap())
+            })
+            .map(|m| m.id)
+            .collect();
+    }
+
+    fn find_institution_of_type(
+        city: &City,
+        institute_type: InstituteType,
+    ) -> Option<&Institution> {
+        return city
+            .institutions
+            .values()
+            .find(|i| i.institute_type.eq(&institute_type));
+    }
+
+    fn offence_chance(mind: &Mind, tier: &WealthTier) -> f32 {
+        let wealth_multiplier = match tier {
+            WealthTier::Poor => POOR_OFFENCE_MULTIPLIER,
+            WealthTier::Common => 1.0,
+            WealthTier::Wealthy => WEALTHY_OFFENCE_MULTIPLIER,
+        };
+        let disposition = (1.0 - mind.personality.honesty) + mind.personality.temper;
+        return BASE_OFFENCE_RATE * wealth_multiplier * disposition;
+    }
+
+    fn choose_offence(mind: &Mind, tier: &WealthTier) -> Offence {
+        let dishonesty = 1.0 - mind.personality.honesty;
+        let youth = if mind.age < YOUTH_OFFENCE_AGE {
+            1.0
+        } else {
+            0.2
+        };
+        let options = vec![
+            (
+                Offence::Theft,
+                dishonesty
+                    * if tier.eq(&WealthTier::Wealthy) {
+                        0.3
+                    } else {
+                        1.0
+                    },
+            ),
+            (
+                Offence::Fraud,
+                dishonesty * if tier.eq(&WealthTier::Poor) { 0.1 } else { 0.6 },
+            ),
+            (Offence::Assault, mind.personality.temper),
+            (Offence::Vandalism, mind.personality.temper * youth),
+        ];
+        return random_weighted_pick(&options);
+    }
+
+    // Violence lands on those the offender already resents, where there is anyone
+    fn choose_victim(mind: &Mind, city: &City) -> Option<Uuid> {
+        let known: Vec<Uuid> = mind
+            .relations
+            .iter()
+            .filter(|(v, id)| {
+                vec![
+                    RelationVerb::Grudge,
+                    RelationVerb::Acquaintance,
+                    RelationVerb::Colleague,
+                ]
+                .contains(v)
+                    && city.citizens.get(id).is_some_and(|m| m.alive)
+            })
+            .map(|(_v, id)| id.clone())
+            .collect();
+        let grudges: Vec<Uuid> = known
+            .iter()
+            .filter(|id| mind.relations.contains(&(RelationVerb::Grudge, **id)))
+            .map(|id| id.clone())
+            .collect();
+        if grudges.len() > 0 {
+            return Some(random_pick(&grudges));
+        }
+        if known.len() > 0 {
+            return Some(random_pick(&known));
+        }
+        return None;
+    }
+
+    fn choose_location(mind: &Mind, offence: &Offence) -> Option<Uuid> {
+        let visit_type = match offence {
+            Offence::Theft => VisitType::Shopping,
+            _ => VisitType::Social,
+        };
+        let (places, _) = get_habitual_institutions(mind, &visit_type);
+        if places.len() < 1 {
+            return mind.employer.clone();
+        }
+        return Some(random_pick(&places).clone());
+    }
+
+    pub fn commit_crimes_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        let mut new_crimes: Vec<Crime> = Vec::new();
+        for mind in city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.age >= city.culture.adult_age && !is_imprisoned(m))
+        {
+            let tier = wealth_tier(mind, city);
+            if rng.gen::<f32>() > offence_chance(mind, &tier) {
+                continue;
+            }
+            let offence = choose_offence(mind, &tier);
+            let victim = match offence {
+                Offence::Assault | Offence::Fraud => choose_victim(mind, city),
+                _ => None,
+            };
+            new_crimes.push(Crime {
+                id: random_uuid(),
+                location: choose_location(mind, &offence),
+                offence,
+                offender: mind.id.clone(),
+                victim,
+                year: city.year,
+                investigator: None,
+                solved: false,
+                verdict: Verdict::Pending,
+            });
+        }
+        for crime in new_crimes {
+            if crime.victim.is_some() {
+                let offender = city.citizens.get(&crime.offender).unwrap().clone();
+                let victim = city.citizens.get_mut(&crime.victim.unwrap()).unwrap();
+                add_event_to_mind_log(
+                    victim,
+                    city.year,
+                    &format!(
+                        "was the victim of {} by {}",
+                        crime.offence,
+                        get_full_name(&offender)
+                    ),
+                );
+                if rng.gen::<f32>() < VICTIM_GRUDGE_CHANCE {
+                    add_grudge(
+                        city,
+                        &crime.victim.unwrap(),
+                        &crime.offender,
+                        GrudgeCause::Crime,
+                    );
+                }
+            }
+            city.crimes.insert(crime.id.clone(), crime);
+        }
+        return city;
+    }
+
+    pub fn investigate_crimes_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        let officers = staff_of(city, InstituteType::PoliceStation);
+        if officers.len() < 1 {
+            return city;
+        }
+        let population = city.citizens.values().filter(|m| m.alive).count() as f32;
+        let staffing = (officers.len() as f32 / (population * POLICE_PER_CAPITA).max(1.0)).min(1.0);
+        let solve_rate = BASE_SOLVE_RATE + POLICE_SOLVE_RATE * staffing;
+        let year = city.year;
+        let open_cases: Vec<Uuid> = city
+            .crimes
+            .values()
+            .filter(|c| !c.solved && c.year + CASE_OPEN_YEARS > year)
+            .map(|c| c.id)
+            .collect();
+        for id in open_cases {
+            let officer_id = random_pick(&officers);
+            let crime = city.crimes.get_mut(&id).unwrap();
+            crime.investigator = Some(officer_id.clone());
+            let offender = city.citizens.get(&crime.offender).unwrap();
+            if rng.gen::<f32>() < solve_rate && offender.alive {
+                crime.solved = true;
+                let offence = crime.offence.clone();
+                let offender_name = get_full_name(offender);
+                let officer = city.citizens.get_mut(&officer_id).unwrap();
+                add_event_to_mind_log(
+                    officer,
+                    year,
+                    &format!("solved a case of {} by {}", offence, offender_name),
+                );
+            }
+        }
+        return city;
+    }
+
+    fn imprison<'a>(
+        city: &'a mut City,
+        prison: &Institution,
+        mind_id: &Uuid,
+        years: usize,
+        offence: &Offence,
+    ) {
+        let (_building, _floor, cell, _location) = find_institution_address(prison, city);
+        let cell_id = cell.id.clone();
+        let prison_name = prison.name.clone();
+        let employer_name = city
+            .citizens
+            .get(mind_id)
+            .unwrap()
+            .employer
+            .map(|id| city.institutions.get(&id).unwrap().name.clone());
+        let year = city.year;
+        leave_household(city, mind_id);
+        let mind = city.citizens.get_mut(mind_id).unwrap();
+        mind.residence = Some(cell_id);
+        mind.employer = None;
+        mind.imprisoned_until = Some(year + years);
+        add_event_to_mind_log(
+            mind,
+            year,
+            &format!(
+                "was sentenced to {} year{} in {} for {}",
+                years,
+                if years == 1 { "" } else { "s" },
+                prison_name,
+                offence
+            ),
+        );
+        if employer_name.is_some() {
+            add_event_to_mind_log(
+                mind,
+                year,
+                &format!("lost their job at {}", employer_name.unwrap()),
+            );
+        }
+    }
+
+    // The offender's household pays what it can of the fine
+    fn fine(city: &mut City, mind_id: &Uuid, amount: i32, offence: &Offence) {
+        let year = city.year;
+        let household_id = city.citizens.get(mind_id).unwrap().household;
+        let mut paid = 0;
+        if household_id.is_some() {
+            let household = city.households.get_mut(&household_id.unwrap()).unwrap();
+            paid = amount.min(household.budget.max(0));
+            household.budget -= paid;
+        }
+        let event = if paid > 0 {
+            format!(
+                "paid a fine of {} for {}, as the city has no prison",
+                paid, offence
+            )
+        } else {
+            format!(
+                "was fined {} for {} but could not pay, as the city has no prison",
+                amount, offence
+            )
+        };
+        add_event_to_mind_log(city.citizens.get_mut(mind_id).unwrap(), year, &event);
+    }
+
+    pub fn sentence_crimes_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        // cases can only be heard while the court has staff
+        if staff_of(city, InstituteType::Court).len() < 1 {
+            return city;
+        }
+        let cases: Vec<Crime> = city
+            .crimes
+            .values()
+            .filter(|c| c.solved && c.verdict.eq(&Verdict::Pending))
+            .map(|c| c.clone())
+            .collect();
+        for case in cases {
+            let offender = city.citizens.get(&case.offender).unwrap();
+            if !offender.alive {
+                continue;
+            }
+            let prison = find_institution_of_type(city, InstituteType::Prison).cloned();
+            let verdict = if rng.gen::<f32>() < CONVICTION_RATE {
+                let (min, max) = sentence_range(&case.offence);
+                let years = min + (rng.gen::<f32>() * (max - min + 1) as f32) as usize;
+                if prison.is_some() || is_imprisoned(offender) {
+                    Verdict::Guilty(years)
+                } else {
+                    Verdict::Fined(years as i32 * FINE_PER_SENTENCE_YEAR)
+                }
+            } else {
+                Verdict::NotGuilty
+            };
+            match verdict {
+                Verdict::Guilty(years) => {
+                    if is_imprisoned(offender) {
+                        // sentences for further offences run on from the current one
+                        let mind = city.citizens.get_mut(&case.offender).unwrap();
+                        mind.imprisoned_until = Some(mind.imprisoned_until.unwrap() + years);
+                    } else {
+                        imprison(city, &prison.unwrap(), &case.offender, years, &case.offence);
+                    }
+                }
+                Verdict::Fined(amount) => fine(city, &case.offender, amount, &case.offence),
+                _ => {
+                    let mind = city.citizens.get_mut(&case.offender).unwrap();
+                    add_event_to_mind_log(
+                        mind,
+                        city.year,
+                        &format!("was found not guilty of {}", case.offence),
+                    );
+                }
+            }
+            city.crimes.get_mut(&case.id).unwrap().verdict = verdict;
+        }
+        return city;
+    }
+
+    // Prisoners who have served their time leave with no home or job to return to
+    pub fn release_prisoners_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let year = city.year;
+        for mind in city
+            .citizens
+            .values_mut()
+            .filter(|m| m.alive && is_imprisoned(m))
+        {
+            if mind.imprisoned_until.unwrap() <= year {
+                mind.imprisoned_until = None;
+                mind.residence = None;
+                add_event_to_mind_log(mind, year, "was released from prison");
+            }
+        }
+        for mind in city
+            .citizens
+            .values_mut()
+            .filter(|m| !m.alive && is_imprisoned(m))
+        {
+            mind.imprisoned_until = None;
+        }
+        return city;
+    }
+
+    pub fn run_justice_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        release_prisoners_per_year(city);
+        commit_crimes_per_year(city);
+        investigate_crimes_per_year(city);
+        sentence_crimes_per_year(city);
+        return city;
+    }
+
+    pub fn print_crime_statistics(city: &City) -> String {
+        let mut output = String::new();
+        let mut by_offence: HashMap<String, usize> = HashMap::default();
+        for crime in city.crimes.values() {
+            *by_offence.entry(crime.offence.to_string()).or_insert(0) += 1;
+        }
+        let solved = city.crimes.values().filter(|c| c.solved).count();
+        let convicted = city
+            .crimes
+            .values()
+            .filter(|c| matches!(c.verdict, Verdict::Guilty(_) | Verdict::Fined(_)))
+            .count();
+        let imprisoned = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && is_imprisoned(m))
+            .count();
+        output.push_str(&format!("Crimes Recorded: {}\n", city.crimes.len()));
+        let mut offences: Vec<(String, usize)> = by_offence.into_iter().collect();
+        offences.sort_by_key(|(o, _c)| o.clone());
+        for (offence, count) in offences {
+            output.push_str(&format!("  {}: {}\n", offence, count));
+        }
+        output.push_str(&format!("Crimes Solved: {}\n", solved));
+        output.push_str(&format!("Convictions: {}\n", convicted));
+        output.push_str(&format!("Prison Population: {}\n", imprisoned));
+        return output;
+    }
+}
@@ -2,6 +2,9 @@ pub mod visits {
     use crate::city::{
         city::City, institutions::institutions::InstituteType, population::mind::mind::Mind,
     };
+    use crate::city::institutions::justice::justice::is_imprisoned;
+    use crate::culture::religion::religion::find_faith;
+    use crate::utils::utils::{random_pick, thread_rng, HashMap};
     use rand::Rng;
     use uuid::Uuid;
     // use rand::seq::SliceRandom;
@@ -11,39 +14,44 @@ pub mod visits {
     const SOCIAL_HABIT_LIMIT: usize = 5;
     const SHOPPING_HABIT_LIMIT: usize = 10;
 
+    // Retirees have more time for cafes and venues and less call for the shops
+    const RETIREE_SOCIAL_VISIT_MULTIPLIER: f32 = 1.5;
+    const RETIREE_SHOPPING_VISIT_MULTIPLIER: f32 = 0.75;
+
+    const WORSHIP_HABIT_LIMIT: usize = 2;
+    const SERVICES_PER_YEAR: f32 = 52.0;
+    const WORSHIP_WANDER_CHANCE: f32 = 0.1;
+
     #[derive(PartialEq)]
     pub enum VisitType {
         Shopping,
         Social,
+        Worship,
+    }
+
+    fn visit_counts<'a>(mind: &'a Mind, visit_type: &VisitType) -> &'a HashMap<Uuid, usize> {
+        return match visit_type {
+            VisitType::Shopping => &mind.institution_shopping_visits,
+            VisitType::Social => &mind.institution_social_visits,
+            VisitType::Worship => &mind.institution_worship_visits,
+        };
     }
 
     pub fn get_habitual_institutions<'a>(
         mind: &'a Mind,
         visit_type: &'a VisitType,
     ) -> (Vec<&'a Uuid>, f32) {
-        let total_visits: usize = if visit_type.eq(&VisitType::Shopping) {
-            &mind.institution_shopping_visits
-        } else {
-            &mind.institution_social_visits
-        }
-        .values()
-        .sum();
+        let total_visits: usize = visit_counts(mind, visit_type).values().sum();
 
         if total_visits.eq(&0) {
             return (Vec::new(), 0.0);
         }
 
-        let mut visits: Vec<(&Uuid, &usize)> = if visit_type.eq(&VisitType::Shopping) {
-            &mind.institution_shopping_visits
-        } else {
-            &mind.institution_social_visits
-        }
-        .iter()
-        .collect();
-        let max_limit = if visit_type.eq(&VisitType::Shopping) {
-            SHOPPING_HABIT_LIMIT
-        } else {
-            SOCIAL_HABIT_LIMIT
+        let mut visits: Vec<(&Uuid, &usize)> = visit_counts(mind, visit_type).iter().collect();
+        let max_limit = match visit_type {
+            VisitType::Shopping => SHOPPING_HABIT_LIMIT,
+            VisitType::Social => SOCIAL_HABIT_LIMIT,
+            VisitType::Worship => WORSHIP_HABIT_LIMIT,
         };
         let limit = if visits.len() > max_limit {
             max_limit
@@ -63,10 +71,10 @@ pub mod visits {
         institutions: &Vec<&Uuid>,
         visit_type: &VisitType,
     ) {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
 
         let mind = city.citizens.get_mut(mind_id).unwrap();
-        if !mind.alive {
+        if !mind.alive || is_imprisoned(mind) {
             return;
         }
         let mind_clone = mind.clone();
@@ -78,7 +86,12 @@ pub mod visits {
             return;
         }
 
-        let visit_count = (rng.gen::<f32>() * 365.0).round() as usize;
+        let visit_multiplier = match visit_type {
+            VisitType::Social if mind_clone.retired => RETIREE_SOCIAL_VISIT_MULTIPLIER,
+            VisitType::Shopping if mind_clone.retired => RETIREE_SHOPPING_VISIT_MULTIPLIER,
+            _ => 1.0,
+        };
+        let visit_count = (rng.gen::<f32>() * 365.0 * visit_multiplier).round() as usize;
         // TODO - this powf value be related to some mind personality value "amount somebody sticks to a habit"
         let habit_visit_odds = habit_scale.powf(0.5);
         for _i in 0..visit_count {
@@ -168,4 +181,45 @@ pub mod visits {
         }
         return city;
     }
+
+    pub fn run_citizen_worship<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        let mut places_by_faith: HashMap<Uuid, Vec<Uuid>> = HashMap::default();
+        for inst in city.institutions.values() {
+            if inst.institute_type.eq(&InstituteType::PlaceOfWorship) && inst.faith.is_some() {
+                places_by_faith
+                    .entry(inst.faith.unwrap())
+                    .or_insert(Vec::new())
+                    .push(inst.id.clone());
+            }
+        }
+        let faiths = city.culture.faiths.clone();
+        for mind in city
+            .citizens
+            .values_mut()
+            .filter(|m| m.alive && m.faith.is_some() && !is_imprisoned(m))
+        {
+            let places = places_by_faith.get(&mind.faith.unwrap());
+            let faith = find_faith(&faiths, &mind.faith.unwrap());
+            if places.is_none() || faith.is_none() {
+                continue;
+            }
+            // most of the faithful settle into a single congregation
+            let regular = mind
+                .institution_worship_visits
+                .iter()
+                .max_by_key(|(_id, count)| **count)
+                .map(|(id, _count)| id.clone());
+            let place = if regular.is_some() && rng.gen::<f32>() > WORSHIP_WANDER_CHANCE {
+                regular.unwrap()
+            } else {
+                random_pick(places.unwrap())
+            };
+            let visit_count =
+                (SERVICES_PER_YEAR * faith.unwrap().devotion * rng.gen::<f32>()).round() as usize;
+            *mind.institution_worship_visits.entry(place).or_insert(0) += visit_count;
+            city.institutions.get_mut(&place).unwrap().annual_visits += visit_count;
+        }
+        return city;
+    }
 }
@@ -0,0 +1,202 @@
+pub mod worship {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::{add_institution_to_city, City};
+    use crate::city::institutions::institutions::{InstituteType, Institution};
+    use crate::city::institutions::justice::justice::is_imprisoned;
+    use crate::city::population::mind::mind::{
+        add_event_to_mind_log, add_leaving_workplace_to_mind_log,
+    };
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::culture::religion::religion::Faith;
+    use crate::language::language::*;
+    use crate::templater::templater::render_template_2;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng, HashMap};
+
+    // Number of faithful a single place of worship can serve
+    const CONGREGATION_SIZE: usize = 120;
+    const PRIEST_MAX: f32 = 4.0;
+
+    pub fn generate_place_of_worship(
+        dict: &Vec<Word>,
+        era: &Option<Era>,
+        faith: &Faith,
+    ) -> Institution {
+        let mut rng = thread_rng();
+        let deity = random_pick(&faith.pantheon);
+        let roll = rng.gen::<f32>();
+        let name = if roll < 0.33 {
+            format!(
+                "{} {} of {}",
+                render_template_2("{{Adjective(Position, Quality, Age, Colour)}}", &dict, era),
+                faith.place_of_worship,
+                deity.name
+            )
+        } else if roll < 0.66 {
+            format!("{}'s {}", deity.name, faith.place_of_worship)
+        } else {
+            format!("{} of {}", faith.place_of_worship, deity.name)
+        };
+        return Institution {
+            id: random_uuid(),
+            name,
+            public: false,
+            institute_type: InstituteType::PlaceOfWorship,
+            size: ((rng.gen::<f32>() * PRIEST_MAX) as usize).max(1),
+            serves: Vec::new(),
+            customer_cost_multipler: 0.0,
+            wealth: 0,
+            faith: Some(faith.id.clone()),
+            annual_visits: 0,
+        };
+    }
+
+    fn places_of_worship_by_faith(city: &City) -> HashMap<Uuid, Vec<Uuid>> {
+        let mut output: HashMap<Uuid, Vec<Uuid>> = HashMap::default();
+        for inst in city.institutions.values() {
+            if inst.institute_type.eq(&InstituteType::PlaceOfWorship) && inst.faith.is_some() {
+                output
+                    .entry(inst.faith.unwrap())
+                    .or_insert(Vec::new())
+                    .push(inst.id.clone());
+            }
+        }
+        return output;
+    }
+
+    // Build new places of worship as a faith's following outgrows the ones it has
+    pub fn found_places_of_worship_per_year<'a>(
+        city: &'a mut City,
+        dict: &Vec<Word>,
+    ) -> &'a mut City {
+        let existing = places_of_worship_by_faith(city);
+        for faith in city.culture.faiths.clone() {
+            let followers = city
+                .citizens
+                .values()
+                .filter(|m| m.alive && m.faith.eq(&Some(faith.id)))
+                .count();
+            let needed = (followers as f32 / CONGREGATION_SIZE as f32).ceil() as usize;
+            let current = existing.get(&faith.id).map(|p| p.len()).unwrap_or(0);
+            for _i in current..needed {
+                let place = generate_place_of_worship(&dict, &city.culture.era, &faith);
+                add_institution_to_city(city, place, &dict);
+            }
+        }
+        return city;
+    }
+
+    // Fill vacant clergy positions from the faithful, and dismiss any priest who has lost their faith
+    pub fn ordain_priests_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let places: Vec<Institution> = city
+            .institutions
+            .values()
+            .filter(|i| i.institute_type.eq(&InstituteType::PlaceOfWorship))
+            .map(|i| i.clone())
+            .collect();
+        for place in places {
+            let priests: Vec<Uuid> = city
+                .citizens
+                .values()
+                .filter(|m| m.alive && m.employer.eq(&Some(place.id)))
+                .map(|m| m.id)
+                .collect();
+            let mut priest_count = priests.len();
+            for id in priests {
+                let priest = city.citizens.get_mut(&id).unwrap();
+                if !priest.faith.eq(&place.faith) {
+                    priest.employer = None;
+                    add_leaving_workplace_to_mind_log(priest, city.year, &place.name);
+                    priest_count -= 1;
+                }
+            }
+            if priest_count >= place.size {
+                continue;
+            }
+            let candidates: Vec<Uuid> = city
+                .citizens
+                .values()
+                .filter(|m| {
+                    m.alive
+                        && m.age >= city.culture.adult_age
+                        && m.employer.is_none()
+                        && !m.retired
+                        && !is_imprisoned(m)
+                        && m.faith.eq(&place.faith)
+                })
+                .map(|m| m.id)
+                .take(place.size - priest_count)
+                .collect();
+            for id in candidates {
+                let priest = city.citizens.get_mut(&id).unwrap();
+                priest.employer = Some(place.id.clone());
+                add_event_to_mind_log(
+                    priest,
+                    city.year,
+                    &format!("was ordained as a priest at the {}", place.name),
+                );
+            }
+        }
+        return city;
+    }
+
+    // Link the faithful to a priest at the place of worship they attend most
+    pub fn link_priests_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut priests_by_place: HashMap<Uuid, Vec<Uuid>> = HashMap::default();
+        for mind in city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.employer.is_some())
+        {
+            let employer = city.institutions.get(&mind.employer.unwrap());
+            if employer.is_some()
+                && employer
+                    .unwrap()
+                    .institute_type
+                    .eq(&InstituteType::PlaceOfWorship)
+            {
+                priests_by_place
+                    .entry(employer.unwrap().id)
+                    .or_insert(Vec::new())
+                    .push(mind.id.clone());
+            }
+        }
+        for mind in city.citizens.values_mut().filter(|m| m.alive) {
+            let current: Vec<Uuid> = mind
+                .relations
+                .iter()
+                .filter(|(v, _id)| v.eq(&RelationVerb::Priest))
+                .map(|(_v, id)| id.clone())
+                .collect();
+            mind.relations
+                .retain(|(v, _id)| !v.eq(&RelationVerb::Priest));
+            let regular = mind
+                .institution_worship_visits
+                .iter()
+                .max_by_key(|(_id, count)| **count)
+                .map(|(id, _count)| id.clone());
+            if regular.is_none() || !priests_by_place.contains_key(&regular.unwrap()) {
+                continue;
+            }
+            let priests: Vec<Uuid> = priests_by_place
+                .get(&regular.unwrap())
+                .unwrap()
+                .iter()
+                .filter(|id| !id.eq(&&mind.id))
+                .map(|id| id.clone())
+                .collect();
+            if priests.len() < 1 {
+                continue;
+            }
+            let kept = current.iter().find(|id| priests.contains(id));
+            let priest = if kept.is_some() {
+                kept.unwrap().clone()
+            } else {
+                random_pick(&priests)
+            };
+            mind.relations.push((RelationVerb::Priest, priest));
+        }
+        return city;
+    }
+}
@@ -10,19 +10,24 @@ pub mod locations {
 
     use crate::language::language::*;
     use crate::templater::templater::*;
-    use crate::utils::utils::random_pick;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng};
+
+    pub const MAX_DESIRABILITY: u32 = 5;
 
     #[derive(PartialEq, Debug, Clone)]
     pub struct Location {
         pub id: Uuid,
         pub name: String,
         pub size: usize,
+        // How sought after homes here are, from 1 to MAX_DESIRABILITY
+        pub desirability: u32,
     }
 
     pub fn print_location(location: &Location, city: &City) -> String {
         let mut output: String = String::new();
         output.push_str("==Location=\n");
         output.push_str(&format!("Name: {}\n", location.name));
+        output.push_str(&format!("Desirability: {}\n", location.desirability));
         output.push_str("Buildings: \n");
         let buildings: Vec<&Building> = city
             .buildings
@@ -55,11 +60,12 @@ pub mod locations {
     }
 
     pub fn gen_location(dict: &Vec<Word>, era: &Option<Era>) -> Location {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         return Location {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             name: gen_location_name(&dict, false, era),
             size: ((rng.gen::<f32>() * 10.0) as i32).max(1) as usize,
+            desirability: 1 + (rng.gen::<f32>() * MAX_DESIRABILITY as f32) as u32,
         };
     }
 
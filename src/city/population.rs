@@ -1,12 +1,14 @@
+pub mod founding_families;
+pub mod household;
 pub mod mind;
 pub mod population {
     use uuid::Uuid;
 
+    use crate::city::population::founding_families::founding_families::generate_founding_families;
     use crate::city::population::mind::mind::*;
-    use crate::language::language::Era;
     use crate::{city::city::City, language::language::Word};
-    use std::collections::HashMap;
     use std::{fs::File, io::Write};
+    use crate::utils::utils::HashMap;
 
     pub type Population = HashMap<Uuid, Mind>;
 
@@ -18,11 +20,25 @@ pub mod population {
         return output;
     }
 
-    fn generate_base_population<'a>(i: usize, dict: &Vec<Word>, era: &Option<Era>) -> Population {
-        let mut output: Population = HashMap::new();
-        for _i in 0..i {
-            let char = random_char(&dict, era, true);
-            output.insert(char.id.clone(), char);
+    // Living citizens counted by decade of age, youngest first
+    pub fn print_population_pyramid(city: &City) -> String {
+        let mut bands: Vec<usize> = Vec::new();
+        for mind in city.citizens.values().filter(|c| c.alive) {
+            let band = (mind.age / 10) as usize;
+            if bands.len() <= band {
+                bands.resize(band + 1, 0);
+            }
+            bands[band] += 1;
+        }
+        let mut output = String::from("Population By Age:\n");
+        for (band, count) in bands.iter().enumerate() {
+            output.push_str(&format!(
+                "  {}-{}: {} {}\n",
+                band * 10,
+                band * 10 + 9,
+                "#".repeat((count + 4) / 5),
+                count
+            ));
         }
         return output;
     }
@@ -33,7 +49,7 @@ pub mod population {
         c: &'a mut City,
     ) -> &'a mut City {
         let mut city = c;
-        city.citizens = generate_base_population(size, &dict, &city.culture.era);
+        generate_founding_families(&dict, size, &mut city);
         return city;
     }
 
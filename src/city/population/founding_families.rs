@@ -0,0 +1,303 @@
+pub mod founding_families {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::household::household::{found_household, join_household};
+    use crate::city::population::mind::appearance::appearance::age_description;
+    use crate::city::population::mind::faith::faith::{adopt_faith, random_faith_id};
+    use crate::city::population::mind::mind::{random_char, Gender, Mind};
+    use crate::city::population::mind::naming::naming::random_noble_title;
+    use crate::city::population::mind::relations::family_by_marriage::family_by_marriage::update_family_by_marriage;
+    use crate::city::population::mind::relations::marriage::marriage::may_marry;
+    use crate::city::population::mind::relations::parental_naming_formats::parental_naming_formats::get_new_couple_last_names;
+    use crate::city::population::mind::relations::parents::parents::{
+        bear_child, bearer, desired_children,
+    };
+    use crate::city::population::mind::relations::partners::partners::{
+        is_single, mutually_compatible,
+    };
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::culture::culture::CultureConfig;
+    use crate::culture::religion::religion::find_faith;
+    use crate::language::language::Word;
+    use crate::utils::utils::thread_rng;
+
+    // Share of founders who arrive alone rather than at the head of a family
+    const SINGLE_FOUNDER_SHARE: f32 = 0.2;
+    // Ages of the eldest generation heading each founding family
+    const ELDER_MIN_AGE: u32 = 45;
+    const ELDER_MAX_AGE: u32 = 72;
+    // Chance a grown citizen has already settled down, and that the couple has married
+    const PARTNERED_CHANCE: f32 = 0.7;
+    const MARRIED_CHANCE: f32 = 0.7;
+    const MAX_PARTNER_AGE_DIFF: u32 = 6;
+    const PARTNER_ATTEMPTS: usize = 10;
+    // Years before a couple's first child and between each one after
+    const MAX_FIRST_BIRTH_DELAY: u32 = 5;
+    const MIN_BIRTH_SPACING: u32 = 1;
+    const MAX_BIRTH_SPACING: u32 = 4;
+    // Single grown children younger than this still live with their parents
+    const LIVING_AT_HOME_AGE: u32 = 24;
+
+    // Younger ages are more likely, giving the population a pyramid shape
+    fn pyramid_age(min_age: u32, max_age: u32) -> u32 {
+        let mut rng = thread_rng();
+        let roll = rng.gen::<f32>().min(rng.gen::<f32>());
+        return min_age + (roll * (max_age - min_age) as f32) as u32;
+    }
+
+    // Gives a newcomer their age, the looks to match it, a title and a faith
+    fn establish_founder(dict: &Vec<Word>, culture: &CultureConfig, founder: &mut Mind, age: u32) {
+        founder.age = age;
+        for year in culture.adult_age..founder.age {
+            age_description(&dict, &mut founder.physical_description, year);
+        }
+        founder.title = random_noble_title(&dict, &culture.era, &founder.gender);
+        let faith_id = random_faith_id(&culture);
+        if faith_id.is_some() {
+            adopt_faith(
+                founder,
+                find_faith(&culture.faiths, &faith_id.unwrap()).unwrap(),
+            );
+        }
+    }
+
+    fn random_founder(dict: &Vec<Word>, culture: &CultureConfig, age: u32) -> Mind {
+        let mut founder = random_char(&dict, culture, true);
+        establish_founder(dict, culture, &mut founder, age);
+        return founder;
+    }
+
+    // A newcomer of a similar age who would settle down with the given citizen
+    fn find_founding_partner(
+        dict: &Vec<Word>,
+        culture: &CultureConfig,
+        mind: &Mind,
+    ) -> Option<Mind> {
+        let mut rng = thread_rng();
+        for _i in 0..PARTNER_ATTEMPTS {
+            let mut candidate = random_char(&dict, culture, true);
+            if !mutually_compatible(mind, &candidate) {
+                continue;
+            }
+            let age_diff = rng.gen_range(0..=MAX_PARTNER_AGE_DIFF);
+            let age = if rng.gen::<bool>() {
+                mind.age + age_diff
+            } else {
+                mind.age.saturating_sub(age_diff).max(culture.adult_age)
+            };
+            establish_founder(dict, culture, &mut candidate, age);
+            return Some(candidate);
+        }
+        return None;
+    }
+
+    // Pairs a couple off, married under the culture's naming formats or simply living together
+    fn settle_couple(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        let mut rng = thread_rng();
+        let m1 = city.citizens.get(id_1).unwrap().clone();
+        let m2 = city.citizens.get(id_2).unwrap().clone();
+        let married = may_marry(&m1, &m2, &city.culture.marriage_customs)
+            && rng.gen::<f32>() < MARRIED_CHANCE;
+        let verb = if married {
+            RelationVerb::Spouse
+        } else {
+            RelationVerb::Partner
+        };
+        let (last_name_1, last_name_2) = if married {
+            get_new_couple_last_names(&m1, &m2, &city.culture)
+        } else {
+            (m1.last_name.clone(), m2.last_name.clone())
+        };
+        for (id, other_id, last_name) in [(id_1, id_2, last_name_1), (id_2, id_1, last_name_2)] {
+            let mind = city.citizens.get_mut(id).unwrap();
+            mind.last_name = last_name;
+            mind.relations.push((verb.clone(), other_id.clone()));
+        }
+        update_family_by_marriage(city, &[id_1.clone(), id_2.clone()]);
+    }
+
+    // The children a family would already have by now, spaced out from the mother's early adulthood
+    fn raise_children(
+        city: &mut City,
+        dict: &Vec<Word>,
+        size: usize,
+        id_1: &Uuid,
+        id_2: Option<&Uuid>,
+    ) -> Vec<Uuid> {
+        let mut rng = thread_rng();
+        let profile = city.culture.fertility.clone();
+        let parent_1 = city.citizens.get(id_1).unwrap().clone();
+        let parent_2 = id_2.map(|id| city.citizens.get(id).unwrap().clone());
+        let (mother, planned) = match &parent_2 {
+            Some(parent_2) => {
+                if parent_1.gender.eq(&parent_2.gender) || !parent_1.fertile || !parent_2.fertile {
+                    return Vec::new();
+                }
+                (
+                    bearer(&parent_1, parent_2).0.clone(),
+                    desired_children(&parent_1, parent_2, &profile),
+                )
+            }
+            None => {
+                if parent_1.gender.eq(&Gender::Male)
+                    || !parent_1.fertile
+                    || rng.gen::<f32>() > profile.single_parent_rate
+                {
+                    return Vec::new();
+                }
+                (
+                    parent_1.clone(),
+                    1 + rng.gen_range(0..profile.desired_children.max(1)) as usize,
+                )
+            }
+        };
+        let mut children: Vec<Uuid> = Vec::new();
+        let mut birth_age = profile.min_age + rng.gen_range(0..=MAX_FIRST_BIRTH_DELAY);
+        for _i in 0..planned {
+            if birth_age >= mother.age
+                || birth_age >= profile.end_age
+                || city.citizens.len() >= size
+            {
+                break;
+            }
+            let child_id = bear_child(city, dict, &parent_1, parent_2.as_ref());
+            let child = city.citizens.get_mut(&child_id).unwrap();
+            child.age = mother.age - birth_age;
+            for year in 1..child.age {
+                age_description(&dict, &mut child.physical_description, year);
+            }
+            children.push(child_id);
+            birth_age += rng.gen_range(MIN_BIRTH_SPACING..=MAX_BIRTH_SPACING);
+        }
+        return children;
+    }
+
+    // A founder's line down to the youngest generation, each grown child perhaps bringing a partner in
+    fn found_family(city: &mut City, dict: &Vec<Word>, size: usize, founder_id: Uuid) {
+        let mut rng = thread_rng();
+        let mut generation = vec![founder_id];
+        while generation.len() > 0 {
+            let mut next_generation: Vec<Uuid> = Vec::new();
+            for id in generation {
+                if city.citizens.len() >= size {
+                    return;
+                }
+                let mind = city.citizens.get(&id).unwrap().clone();
+                if mind.age < city.culture.adult_age {
+                    continue;
+                }
+                let partner = if rng.gen::<f32>() < PARTNERED_CHANCE {
+                    find_founding_partner(dict, &city.culture, &mind)
+                } else {
+                    None
+                };
+                let partner_id = match partner {
+                    Some(partner) => {
+                        let partner_id = partner.id.clone();
+                        city.citizens.insert(partner_id, partner);
+                        settle_couple(city, &id, &partner_id);
+                        Some(partner_id)
+                    }
+                    None => None,
+                };
+                next_generation.extend(raise_children(city, dict, size, &id, partner_id.as_ref()));
+            }
+            generation = next_generation;
+        }
+    }
+
+    // Couples make a home together with their children; everyone else lives alone
+    fn settle_founding_households(city: &mut City) {
+        let mut eldest_first: Vec<(Uuid, u32)> =
+            city.citizens.values().map(|c| (c.id, c.age)).collect();
+        eldest_first.sort_by_key(|(_id, age)| u32::MAX - age);
+        for (id, _age) in eldest_first {
+            if city.citizens.get(&id).unwrap().household.is_some() {
+                continue;
+            }
+            // a young single parent still living at home brings their own children too
+            let mut members: Vec<Uuid> = vec![id.clone()];
+            let mut next = 0;
+            while next < members.len() {
+                let mind = city.citizens.get(&members[next]).unwrap();
+                next += 1;
+                for (v, rel_id) in mind.relations.iter() {
+                    let relation = || city.citizens.get(rel_id).unwrap();
+                    let moves_in = match v {
+                        RelationVerb::Partner | RelationVerb::Spouse => true,
+                        RelationVerb::Child => {
+                            relation().age < city.culture.adult_age
+                                || (relation().age < LIVING_AT_HOME_AGE && is_single(relation()))
+                        }
+                        _ => false,
+                    };
+                    if moves_in && relation().household.is_none() && !members.contains(rel_id) {
+                        members.push(rel_id.clone());
+                    }
+                }
+            }
+            let household_id = found_household(city, &id);
+            for member in members.iter().skip(1) {
+                join_household(city, &household_id, member);
+            }
+        }
+    }
+
+    // Seeds the city with families already several generations deep
+    pub fn generate_founding_families<'a>(
+        dict: &Vec<Word>,
+        size: usize,
+        city: &'a mut City,
+    ) -> &'a mut City {
+        let mut rng = thread_rng();
+        while city.citizens.len() < size {
+            if rng.gen::<f32>() < SINGLE_FOUNDER_SHARE {
+                let age = pyramid_age(city.culture.adult_age, ELDER_MAX_AGE);
+                let founder = random_founder(dict, &city.culture, age);
+                city.citizens.insert(founder.id.clone(), founder);
+            } else {
+                let age = pyramid_age(ELDER_MIN_AGE, ELDER_MAX_AGE);
+                let founder = random_founder(dict, &city.culture, age);
+                let founder_id = founder.id.clone();
+                city.citizens.insert(founder_id, founder);
+                found_family(city, dict, size, founder_id);
+            }
+        }
+        settle_founding_households(city);
+        // births and weddings before the city's founding are not part of its history
+        for mind in city.citizens.values_mut() {
+            mind.activity_log.clear();
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_founding_population_has_families() {
+        use crate::city::city::simulate;
+        let city = simulate(200, 0, None);
+        assert!(city.citizens.len() >= 200);
+        let minors: Vec<&Mind> = city
+            .citizens
+            .values()
+            .filter(|c| c.age < city.culture.adult_age)
+            .collect();
+        assert!(minors.len() > 0);
+        for minor in minors {
+            assert!(minor.relations.iter().any(|(v, id)| {
+                v.eq(&RelationVerb::Parent)
+                    && city
+                        .citizens
+                        .get(id)
+                        .unwrap()
+                        .household
+                        .eq(&minor.household)
+            }));
+        }
+        assert!(city.citizens.values().any(|c| c
+            .relations
+            .iter()
+            .any(|(v, _id)| v.eq(&RelationVerb::Grandparent))));
+    }
+}
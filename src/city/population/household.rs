@@ -0,0 +1,349 @@
+pub mod household {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::justice::justice::is_imprisoned;
+    use crate::city::population::mind::appearance::appearance::{wealth_tier, WealthTier};
+    use crate::city::population::mind::mind::Mind;
+    use crate::city::population::mind::naming::naming::get_full_name;
+    use crate::city::population::mind::relations::custody::custody::{expire_custody, Custody};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::{random_uuid, thread_rng};
+
+    // Annual chance an adult child still living with family moves out
+    const LEAVE_HOME_CHANCE: f32 = 0.25;
+    // Annual chance unmarried partners living apart move in together
+    const COHABIT_CHANCE: f32 = 0.3;
+    const WEALTHY_INCOME: i32 = 40;
+    const COMMON_INCOME: i32 = 20;
+    const POOR_INCOME: i32 = 8;
+    const MEMBER_UPKEEP: i32 = 5;
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Household {
+        pub id: Uuid,
+        pub head: Uuid,
+        pub members: Vec<Uuid>,
+        // Building floor area the household lives in
+        pub dwelling: Option<Uuid>,
+        pub budget: i32,
+        // Rent owed to the landlord and not yet paid
+        pub rent_arrears: i32,
+        // Year the household last found itself without a home
+        pub homeless_since: Option<usize>,
+    }
+
+    pub fn find_household<'a>(mind: &Mind, city: &'a City) -> Option<&'a Household> {
+        return mind.household.map(|id| city.households.get(&id)).flatten();
+    }
+
+    pub fn leave_household(city: &mut City, mind_id: &Uuid) {
+        let mind = city.citizens.get_mut(mind_id).unwrap();
+        let household_id = mind.household.take();
+        if household_id.is_none() {
+            return;
+        }
+        let household = city.households.get_mut(&household_id.unwrap()).unwrap();
+        household.members.retain(|id| !id.eq(mind_id));
+        if household.members.len() < 1 {
+            city.households.remove(&household_id.unwrap());
+            return;
+        }
+        if household.head.eq(mind_id) {
+            // the eldest remaining member takes over
+            let members = household.members.clone();
+            let new_head = members
+                .iter()
+                .max_by_key(|id| city.citizens.get(id).unwrap().age)
+                .unwrap();
+            city.households
+                .get_mut(&household_id.unwrap())
+                .unwrap()
+                .head = new_head.clone();
+        }
+    }
+
+    pub fn join_household(city: &mut City, household_id: &Uuid, mind_id: &Uuid) {
+        leave_household(city, mind_id);
+        let household = city.households.get_mut(household_id).unwrap();
+        household.members.push(mind_id.clone());
+        let dwelling = household.dwelling.clone();
+        let mind = city.citizens.get_mut(mind_id).unwrap();
+        mind.household = Some(household_id.clone());
+        mind.residence = dwelling;
+    }
+
+    // Set up a new household with no home yet, headed by the given citizen
+    pub fn found_household(city: &mut City, mind_id: &Uuid) -> Uuid {
+        leave_household(city, mind_id);
+        let household = Household {
+            id: random_uuid(),
+            head: mind_id.clone(),
+            members: vec![mind_id.clone()],
+            dwelling: None,
+            budget: 0,
+            rent_arrears: 0,
+            homeless_since: Some(city.year),
+        };
+        let mind = city.citizens.get_mut(mind_id).unwrap();
+        mind.household = Some(household.id.clone());
+        mind.residence = None;
+        let id = household.id.clone();
+        city.households.insert(id.clone(), household);
+        return id;
+    }
+
+    pub fn set_dwelling(city: &mut City, household_id: &Uuid, dwelling: Option<Uuid>) {
+        let year = city.year;
+        let household = city.households.get_mut(household_id).unwrap();
+        household.dwelling = dwelling.clone();
+        household.homeless_since = match dwelling {
+            Some(_) => None,
+            None => household.homeless_since.or(Some(year)),
+        };
+        for id in household.members.iter() {
+            city.citizens.get_mut(id).unwrap().residence = dwelling.clone();
+        }
+    }
+
+    // The custodial parent is preferred over one the child only visits
+    fn living_guardian_household(mind: &Mind, city: &City) -> Option<Uuid> {
+        let mut guardians: Vec<&Mind> = mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+            .map(|(_v, id)| city.citizens.get(id).unwrap())
+            .filter(|g| g.alive && !is_imprisoned(g) && g.household.is_some())
+            .collect();
+        guardians.sort_by_key(|g| mind.custody.get(&g.id).eq(&Some(&Custody::Visitation)));
+        return guardians.first().map(|g| g.household.unwrap());
+    }
+
+    fn partner_of<'a>(mind: &Mind, city: &'a City) -> Option<(RelationVerb, &'a Mind)> {
+        return mind
+            .relations
+            .iter()
+            .find(|(v, _id)| v.eq(&RelationVerb::Partner) || v.eq(&RelationVerb::Spouse))
+            .map(|(v, id)| (v.clone(), city.citizens.get(id).unwrap()))
+            .filter(|(_v, p)| p.alive && !is_imprisoned(p));
+    }
+
+    // The dead and imprisoned drop out of their households
+    fn remove_absent_members(city: &mut City) {
+        let absent: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.household.is_some() && (!m.alive || is_imprisoned(m)))
+            .map(|m| m.id)
+            .collect();
+        for id in absent {
+            leave_household(city, &id);
+        }
+    }
+
+    // Citizens without a household join a guardian's, or found their own once grown
+    fn house_the_unhoused(city: &mut City) {
+        let unhoused: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && !is_imprisoned(m) && m.household.is_none())
+            .map(|m| m.id)
+            .collect();
+        for id in unhoused {
+            let mind = city.citizens.get(&id).unwrap();
+            let guardian_household = if mind.age < city.culture.adult_age {
+                living_guardian_household(mind, city)
+            } else {
+                None
+            };
+            match guardian_household {
+                Some(household_id) => join_household(city, &household_id, &id),
+                None => {
+                    found_household(city, &id);
+                }
+            }
+        }
+    }
+
+    // Couples living apart move in together, bringing their young children with them
+    fn merge_couples(city: &mut City) {
+        let mut rng = thread_rng();
+        let mut moves: Vec<(Uuid, Uuid)> = Vec::new();
+        for mind in city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.household.is_some() && m.age >= city.culture.adult_age)
+        {
+            let partner = partner_of(mind, city);
+            if partner.is_none() {
+                continue;
+            }
+            let (verb, partner) = partner.unwrap();
+            // each couple is considered once, from the side with the lower id
+            if partner.household.is_none()
+                || partner.household.eq(&mind.household)
+                || partner.age < city.culture.adult_age
+                || mind.id > partner.id
+                || moves
+                    .iter()
+                    .any(|(m, _h)| m.eq(&partner.id) || m.eq(&mind.id))
+            {
+                continue;
+            }
+            if verb.eq(&RelationVerb::Partner) && rng.gen::<f32>() > COHABIT_CHANCE {
+                continue;
+            }
+            let mind_household = find_household(mind, city).unwrap();
+            let partner_household = find_household(partner, city).unwrap();
+            // whoever has no home, or the smaller household, moves in with the other
+            let mind_moves = (mind_household.dwelling.is_none()
+                && partner_household.dwelling.is_some())
+                || (mind_household.dwelling.is_some() == partner_household.dwelling.is_some()
+                    && mind_household.members.len() < partner_household.members.len());
+            let (mover, target) = if mind_moves {
+                (mind, partner_household)
+            } else {
+                (partner, mind_household)
+            };
+            moves.push((mover.id.clone(), target.id.clone()));
+            for (v, child_id) in mover.relations.iter() {
+                if !(v.eq(&RelationVerb::Child) || v.eq(&RelationVerb::AdoptedChild)) {
+                    continue;
+                }
+                let child = city.citizens.get(child_id).unwrap();
+                if child.alive
+                    && child.age < city.culture.adult_age
+                    && child.household.eq(&mover.household)
+                {
+                    moves.push((child_id.clone(), target.id.clone()));
+                }
+            }
+        }
+        for (mind_id, household_id) in moves {
+            if city.households.contains_key(&household_id) {
+                join_household(city, &household_id, &mind_id);
+            }
+        }
+    }
+
+    // Grown children living with family eventually strike out on their own
+    fn leave_home(city: &mut City) {
+        let mut rng = thread_rng();
+        let leaving: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.age >= city.culture.adult_age && m.household.is_some())
+            .filter(|m| {
+                let household = find_household(m, city).unwrap();
+                let head = city.citizens.get(&household.head).unwrap();
+                !household.head.eq(&m.id)
+                    && !partner_of(m, city).is_some_and(|(_v, p)| p.id.eq(&head.id))
+                    && m.relations.iter().any(|(v, id)| {
+                        (v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+                            && household.members.contains(id)
+                    })
+            })
+            .filter(|_m| rng.gen::<f32>() < LEAVE_HOME_CHANCE)
+            .map(|m| m.id)
+            .collect();
+        for id in leaving {
+            // a partner and young children living in the same household go with them
+            let mind = city.citizens.get(&id).unwrap();
+            let followers: Vec<Uuid> = mind
+                .relations
+                .iter()
+                .filter(|(v, _id)| {
+                    v.eq(&RelationVerb::Partner)
+                        || v.eq(&RelationVerb::Spouse)
+                        || v.eq(&RelationVerb::Child)
+                        || v.eq(&RelationVerb::AdoptedChild)
+                })
+                .map(|(_v, id)| city.citizens.get(id).unwrap())
+                .filter(|m| {
+                    m.alive
+                        && m.household.eq(&mind.household)
+                        && (m.age < city.culture.adult_age
+                            || partner_of(mind, city).is_some_and(|(_v, p)| p.id.eq(&m.id)))
+                })
+                .map(|m| m.id.clone())
+                .collect();
+            let household_id = found_household(city, &id);
+            for follower in followers {
+                join_household(city, &household_id, &follower);
+            }
+        }
+    }
+
+    fn member_income(mind: &Mind, city: &City) -> i32 {
+        if mind.age < city.culture.adult_age {
+            return 0;
+        }
+        return match wealth_tier(mind, city) {
+            WealthTier::Wealthy => WEALTHY_INCOME,
+            WealthTier::Common => COMMON_INCOME,
+            WealthTier::Poor => POOR_INCOME,
+        };
+    }
+
+    pub fn household_income(household: &Household, city: &City) -> i32 {
+        return household
+            .members
+            .iter()
+            .map(|id| member_income(city.citizens.get(id).unwrap(), city))
+            .sum();
+    }
+
+    // What is left of the household's income once its members are provided for
+    pub fn household_surplus(household: &Household, city: &City) -> i32 {
+        return household_income(household, city) - MEMBER_UPKEEP * household.members.len() as i32;
+    }
+
+    // Members pool their income into the household and draw their upkeep from it
+    fn update_budgets(city: &mut City) {
+        let changes: Vec<(Uuid, i32)> = city
+            .households
+            .values()
+            .map(|h| (h.id.clone(), household_surplus(h, city)))
+            .collect();
+        for (id, change) in changes {
+            let household = city.households.get_mut(&id).unwrap();
+            household.budget = (household.budget + change).max(0);
+        }
+    }
+
+    pub fn update_households_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        remove_absent_members(city);
+        expire_custody(city);
+        house_the_unhoused(city);
+        merge_couples(city);
+        leave_home(city);
+        update_budgets(city);
+        return city;
+    }
+
+    pub fn describe_household(mind: &Mind, city: &City) -> String {
+        let household = find_household(mind, city);
+        if household.is_none() {
+            return String::from("None");
+        }
+        let household = household.unwrap();
+        let head = city.citizens.get(&household.head).unwrap();
+        let mut description = format!(
+            "{} member(s), headed by {}, budget {}",
+            household.members.len(),
+            get_full_name(head),
+            household.budget
+        );
+        if household.homeless_since.is_some() {
+            description.push_str(&format!(
+                ", homeless since year {}",
+                household.homeless_since.unwrap()
+            ));
+        }
+        if household.rent_arrears > 0 {
+            description.push_str(&format!(", {} in rent arrears", household.rent_arrears));
+        }
+        return description;
+    }
+}
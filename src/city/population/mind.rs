@@ -1,8 +1,12 @@
 pub mod appearance;
+pub mod faith;
+pub mod genetics;
+pub mod naming;
+pub mod personality;
 pub mod relations;
+pub mod retirement;
 pub mod mind {
 
-    use std::collections::HashMap;
 
     use rand::Rng;
     use rand_distr::{Distribution, Normal};
@@ -14,18 +18,34 @@ pub mod mind {
     use crate::city::institutions::institutions::*;
     use crate::city::institutions::visits::visits::get_habitual_institutions;
     use crate::city::locations::locations::Location;
+    use crate::city::population::household::household::describe_household;
+    use crate::city::population::mind::relations::residences::residences::describe_housing_need;
+    use crate::city::population::mind::relations::widowhood::widowhood::is_in_mourning;
     use crate::city::population::population::Population;
+    use crate::culture::culture::{
+        era_gender_distribution, era_sexuality_distribution, CultureConfig,
+    };
     use crate::language::language::{random_word_by_tag_and, Era, Word, WordType};
+    use crate::utils::utils::{random_uuid, random_weighted_pick, thread_rng, HashMap};
 
+    use crate::city::population::mind::relations::custody::custody::Custody;
+    use crate::city::population::mind::relations::grudges::grudges::{
+        describe_grudge_cause, GrudgeCause,
+    };
     use crate::city::population::mind::relations::relations::*;
     use crate::language2::language2::Dictionary;
     use crate::language2::names::names::name;
 
     use super::appearance::appearance::{
-        empty_description, random_mind_description, PhysicalDescription,
+        describe_appearance, empty_description, random_mind_description, PhysicalDescription,
+        WealthTier,
     };
+    use super::faith::faith::describe_faith;
+    use super::genetics::genetics::{random_genotype, Genotype};
+    use super::personality::personality::{describe_personality, random_personality, Personality};
+    use super::naming::naming::{get_full_name, random_first_name, random_middle_names};
 
-    const HOMOSEXUALITY_CHANCE: f32 = 0.2;
+    const INFERTILITY_RATE: f32 = 0.1;
 
     #[derive(PartialEq, Debug, Clone, Display)]
     pub enum Gender {
@@ -42,7 +62,7 @@ pub mod mind {
 
     pub type Relation = (RelationVerb, Uuid);
 
-    #[derive(PartialEq, Debug, Clone)]
+    #[derive(PartialEq, Debug, Clone, Display)]
     pub enum Sexuality {
         Hetrosexual,
         Homosexual,
@@ -54,18 +74,40 @@ pub mod mind {
     pub struct Mind {
         pub id: Uuid,
         pub first_name: String,
+        pub middle_names: Vec<String>,
         pub last_name: String,
+        pub nickname: Option<String>,
+        pub title: Option<String>,
         pub gender: Gender,
         pub age: u32,
         pub relations: Vec<Relation>,
+        pub affinities: HashMap<Uuid, i32>,
+        pub grudge_causes: HashMap<Uuid, GrudgeCause>,
+        // Custody between separated parents and their minor children, keyed by the other party
+        pub custody: HashMap<Uuid, Custody>,
         pub employer: Option<Uuid>,
         pub residence: Option<Uuid>,
+        pub household: Option<Uuid>,
         pub sexuality: Sexuality,
+        pub fertile: bool,
         pub alive: bool,
         pub activity_log: Vec<String>,
         pub physical_description: PhysicalDescription,
+        pub genotype: Genotype,
         pub institution_shopping_visits: HashMap<Uuid, usize>,
         pub institution_social_visits: HashMap<Uuid, usize>,
+        pub institution_worship_visits: HashMap<Uuid, usize>,
+        pub faith: Option<Uuid>,
+        pub personality: Personality,
+        // The year a prisoner is due to be released
+        pub imprisoned_until: Option<usize>,
+        pub retired: bool,
+        // Income tier paid out in retirement, or None when left to family support
+        pub pension: Option<WealthTier>,
+        // Year the citizen last lost a partner to death
+        pub bereaved_in: Option<usize>,
+        // Whether the bereaved will look for a new partner once their mourning is over
+        pub will_remarry: bool,
     }
 
     pub fn find_address<'a>(
@@ -102,9 +144,8 @@ pub mod mind {
         let result = population.get(id);
         if result.is_some() {
             return format!(
-                "{} {} {}",
-                String::from(&result.unwrap().first_name),
-                String::from(&result.unwrap().last_name),
+                "{} {}",
+                get_full_name(&result.unwrap()),
                 if result.unwrap().alive {
                     ""
                 } else {
@@ -131,22 +172,38 @@ pub mod mind {
         let mut relations: Vec<(&RelationVerb, String)> = mind
             .relations
             .iter()
-            .map(|(verb, id)| (verb, get_name_from_id(&id, &city.citizens)))
+            .filter(|(verb, _id)| !verb.eq(&RelationVerb::Diety))
+            .map(|(verb, id)| {
+                let name = get_name_from_id(&id, &city.citizens);
+                let name = match mind.grudge_causes.get(id) {
+                    Some(cause) if verb.eq(&RelationVerb::Grudge) => {
+                        format!("{} over {}", name, describe_grudge_cause(cause))
+                    }
+                    _ => name,
+                };
+                let name = match mind.custody.get(id) {
+                    Some(custody) => format!("{} ({})", name, custody),
+                    None => name,
+                };
+                match mind.affinities.get(id) {
+                    Some(score) => (verb, format!("{} (Affinity: {})", name, score)),
+                    None => (verb, name),
+                }
+            })
             .collect();
         relations.sort_by_key(|a| a.0.to_string());
-        output.push_str(&format!("Name: {} {}\n", mind.first_name, mind.last_name));
+        output.push_str(&format!("Name: {}\n", get_full_name(&mind)));
         output.push_str(&format!("Gender: {:?}\n", mind.gender));
         output.push_str(&format!("Age: {}\n", mind.age));
-        let description = &mind.physical_description;
         output.push_str(&format!(
-            "Description: They have {}, {} {} hair and {} eyes. They are {} with a {} build.\n",
-            description.hair_adjectives.first().unwrap(),
-            description.hair_colour,
-            description.hair_length,
-            description.eye_colour,
-            description.height_adjective,
-            description.build_adjective
+            "Description: {}\n",
+            describe_appearance(&mind.physical_description)
+        ));
+        output.push_str(&format!(
+            "Personality: {}\n",
+            describe_personality(&mind.personality)
         ));
+        output.push_str(&format!("Faith: {}\n", describe_faith(&mind, &city.culture)));
         if workplace.is_some() {
             let (building, _floor, area, workplace_location) =
                 find_institution_address(&workplace.unwrap(), &city);
@@ -157,16 +214,31 @@ pub mod mind {
                 building.name,
                 workplace_location.name
             ));
-            if mind.residence.is_some() {
-                let (building, apartment, residential_location) = find_address(mind, city);
-                output.push_str(&format!(
-                    "Lives at: {} {} in {}\n",
-                    apartment.name, building.name, residential_location.name
-                ));
-            }
+        } else if mind.retired {
+            output.push_str("Employer: Retired\n");
         } else {
             output.push_str("Employer: None\n");
         }
+        if is_in_mourning(&mind, &city) {
+            output.push_str(&format!(
+                "Mourning: since year {}\n",
+                mind.bereaved_in.unwrap()
+            ));
+        }
+        let housing_need = describe_housing_need(&mind, &city);
+        if mind.residence.is_some() {
+            let (building, apartment, residential_location) = find_address(mind, city);
+            output.push_str(&format!(
+                "Lives at: {} {} in {}{}\n",
+                apartment.name,
+                building.name,
+                residential_location.name,
+                housing_need.map(|n| format!(" ({})", n)).unwrap_or_default()
+            ));
+        } else {
+            output.push_str(&format!("Lives at: {}\n", housing_need.unwrap()));
+        }
+        output.push_str(&format!("Household: {}\n", describe_household(&mind, &city)));
         output.push_str(&format!("Relations:\n"));
         if relations.len() < 1 {
             output.push_str(&format!("  None\n"));
@@ -211,37 +283,20 @@ pub mod mind {
         return output;
     }
 
-    fn gen_sexuality() -> Sexuality {
-        let mut rng = rand::thread_rng();
-        let roll: f32 = rng.gen();
-        if roll < HOMOSEXUALITY_CHANCE {
-            return Sexuality::Homosexual;
-        } else {
-            return Sexuality::Hetrosexual;
-        }
+    fn gen_sexuality(distribution: &Vec<(Sexuality, f32)>) -> Sexuality {
+        return random_weighted_pick(distribution);
     }
 
-    pub fn random_char<'a>(dict: &Vec<Word>, era: &Option<Era>, gen_last_name: bool) -> Mind {
-        let mut rng = rand::thread_rng();
-        let roll: f32 = rng.gen();
-        let mut gender = Gender::Ambiguous;
-        if roll > 0.6 {
-            gender = Gender::Male;
-        } else if roll > 0.2 {
-            gender = Gender::Female;
-        }
-        let mut first_name_tags = if gender.eq(&Gender::Ambiguous) {
-            vec![String::from("FirstName")]
-        } else {
-            vec![String::from("FirstName"), format!("Gender{}", gender)]
-        };
-        if era.is_some() {
-            first_name_tags.push(era.unwrap().to_string());
-        }
-        let first_name = random_word_by_tag_and(&dict, WordType::Noun, first_name_tags)
-            .unwrap()
-            .text
-            .clone();
+    fn gen_gender(distribution: &Vec<(Gender, f32)>) -> Gender {
+        return random_weighted_pick(distribution);
+    }
+
+    pub fn random_char<'a>(dict: &Vec<Word>, culture: &CultureConfig, gen_last_name: bool) -> Mind {
+        let mut rng = thread_rng();
+        let era = &culture.era;
+        let gender = gen_gender(&culture.gender_distribution);
+        let first_name = random_first_name(&dict, &gender, era);
+        let middle_names = random_middle_names(&dict, &gender, era, &vec![]);
 
         let last_name = if gen_last_name {
             random_word_by_tag_and(&dict, WordType::Noun, vec![String::from("LastName")])
@@ -252,35 +307,47 @@ pub mod mind {
             String::new()
         };
         let distribution = Normal::new(5.0, 10.0).unwrap();
+        let genotype = random_genotype();
         return Mind {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             first_name,
+            middle_names,
             last_name,
+            nickname: None,
+            title: None,
             gender,
             relations: Vec::new(),
+            affinities: HashMap::default(),
+            grudge_causes: HashMap::default(),
+            custody: HashMap::default(),
             age: (rng.gen::<f32>() * 40.0) as u32
                 + 15
-                + distribution.sample(&mut rand::thread_rng()) as u32,
+                + distribution.sample(&mut thread_rng()) as u32,
             employer: None,
             residence: None,
-            sexuality: gen_sexuality(),
+            household: None,
+            sexuality: gen_sexuality(&culture.sexuality_distribution),
+            fertile: rng.gen::<f32>() > INFERTILITY_RATE,
             alive: true,
             activity_log: Vec::new(),
-            physical_description: random_mind_description(&dict),
-            institution_shopping_visits: HashMap::new(),
-            institution_social_visits: HashMap::new(),
+            physical_description: random_mind_description(&dict, &genotype, era),
+            genotype,
+            institution_shopping_visits: HashMap::default(),
+            institution_social_visits: HashMap::default(),
+            institution_worship_visits: HashMap::default(),
+            faith: None,
+            personality: random_personality(),
+            imprisoned_until: None,
+            retired: false,
+            pension: None,
+            bereaved_in: None,
+            will_remarry: false,
         };
     }
 
     pub fn random_char2<'a>(dict: &Dictionary, era: &Option<Era>) -> Mind {
-        let mut rng = rand::thread_rng();
-        let roll: f32 = rng.gen();
-        let mut gender = Gender::Ambiguous;
-        if roll > 0.6 {
-            gender = Gender::Male;
-        } else if roll > 0.2 {
-            gender = Gender::Female;
-        }
+        let mut rng = thread_rng();
+        let gender = gen_gender(&era_gender_distribution(era));
         let era_string = if era.is_some() {
             Some(era.unwrap().to_string())
         } else {
@@ -289,23 +356,41 @@ pub mod mind {
         let (first_name, last_name) = name(&dict, &gender, era_string);
 
         let distribution = Normal::new(5.0, 10.0).unwrap();
+        let genotype = random_genotype();
         return Mind {
-            id: Uuid::new_v4(),
+            id: random_uuid(),
             first_name,
+            middle_names: Vec::new(),
             last_name,
+            nickname: None,
+            title: None,
             gender,
             relations: Vec::new(),
+            affinities: HashMap::default(),
+            grudge_causes: HashMap::default(),
+            custody: HashMap::default(),
             age: (rng.gen::<f32>() * 40.0) as u32
                 + 15
-                + distribution.sample(&mut rand::thread_rng()) as u32,
+                + distribution.sample(&mut thread_rng()) as u32,
             employer: None,
             residence: None,
-            sexuality: gen_sexuality(),
+            household: None,
+            sexuality: gen_sexuality(&era_sexuality_distribution(era)),
+            fertile: rng.gen::<f32>() > INFERTILITY_RATE,
             alive: true,
             activity_log: Vec::new(),
             physical_description: empty_description(),
-            institution_shopping_visits: HashMap::new(),
-            institution_social_visits: HashMap::new(),
+            genotype,
+            institution_shopping_visits: HashMap::default(),
+            institution_social_visits: HashMap::default(),
+            institution_worship_visits: HashMap::default(),
+            faith: None,
+            personality: random_personality(),
+            imprisoned_until: None,
+            retired: false,
+            pension: None,
+            bereaved_in: None,
+            will_remarry: false,
         };
     }
 
@@ -316,18 +401,52 @@ pub mod mind {
         relation: &Mind,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} gained {} {} as a {}",
+            "Year: {} - {} aged {} gained {} as a {}",
             year,
-            mind.first_name,
-            mind.last_name,
+            get_full_name(mind),
             mind.age,
-            relation.first_name,
-            relation.last_name,
+            get_full_name(relation),
             verb
         ));
         return mind;
     }
 
+    pub fn add_grudge_to_mind_log<'a>(
+        mind: &'a mut Mind,
+        year: usize,
+        relation: &Mind,
+        cause: &str,
+    ) -> &'a mut Mind {
+        mind.activity_log.push(format!(
+            "Year: {} - {} aged {} began holding a grudge against {} over {}",
+            year,
+            get_full_name(mind),
+            mind.age,
+            get_full_name(relation),
+            cause
+        ));
+        return mind;
+    }
+
+    pub fn add_adoption_to_mind_log<'a>(
+        mind: &'a mut Mind,
+        year: usize,
+        adopters: &Vec<Mind>,
+    ) -> &'a mut Mind {
+        let names: Vec<String> = adopters
+            .iter()
+            .map(get_full_name)
+            .collect();
+        mind.activity_log.push(format!(
+            "Year: {} - {} aged {} was adopted by {}",
+            year,
+            get_full_name(mind),
+            mind.age,
+            names.join(" and ")
+        ));
+        return mind;
+    }
+
     pub fn add_romatic_event_to_mind_log<'a>(
         mind: &'a mut Mind,
         year: usize,
@@ -336,15 +455,13 @@ pub mod mind {
         action: &str,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} {} {} {} {}",
+            "Year: {} - {} aged {} {} {} {}",
             year,
-            mind.first_name,
-            mind.last_name,
+            get_full_name(mind),
             mind.age,
             action,
             verb,
-            relation.first_name,
-            relation.last_name
+            get_full_name(relation)
         ));
         return mind;
     }
@@ -357,10 +474,9 @@ pub mod mind {
         location_name: &str,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} moved into {} {} in {}",
+            "Year: {} - {} aged {} moved into {} {} in {}",
             year,
-            mind.first_name,
-            mind.last_name,
+            get_full_name(mind),
             mind.age,
             area_name,
             building_name,
@@ -369,6 +485,36 @@ pub mod mind {
         return mind;
     }
 
+    // Logs an event phrased to follow the citizen's name and age, e.g. "retired from the mill"
+    pub fn add_event_to_mind_log<'a>(
+        mind: &'a mut Mind,
+        year: usize,
+        event: &str,
+    ) -> &'a mut Mind {
+        mind.activity_log.push(format!(
+            "Year: {} - {} aged {} {}",
+            year, get_full_name(mind), mind.age, event
+        ));
+        return mind;
+    }
+
+    pub fn add_custody_to_mind_log<'a>(
+        mind: &'a mut Mind,
+        year: usize,
+        custodian: &Mind,
+        visitor: &Mind,
+    ) -> &'a mut Mind {
+        mind.activity_log.push(format!(
+            "Year: {} - {} aged {} saw their parents separate and went to live with {}, visiting {}",
+            year,
+            get_full_name(mind),
+            mind.age,
+            get_full_name(custodian),
+            get_full_name(visitor)
+        ));
+        return mind;
+    }
+
     pub fn add_birth_to_mind_log<'a>(
         mind: &'a mut Mind,
         year: usize,
@@ -376,14 +522,23 @@ pub mod mind {
         parent_2: &Mind,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} born to {} {} and {} {}",
+            "Year: {} - {} born to {} and {}",
             year,
-            mind.first_name,
-            mind.last_name,
-            parent_1.first_name,
-            parent_1.last_name,
-            parent_2.first_name,
-            parent_2.last_name
+            get_full_name(mind),
+            get_full_name(parent_1),
+            get_full_name(parent_2)
+        ));
+        return mind;
+    }
+
+    pub fn add_single_parent_birth_to_mind_log<'a>(
+        mind: &'a mut Mind,
+        year: usize,
+        parent: &Mind,
+    ) -> &'a mut Mind {
+        mind.activity_log.push(format!(
+            "Year: {} - {} born to {}, who raised them alone",
+            year, get_full_name(mind), get_full_name(parent)
         ));
         return mind;
     }
@@ -394,8 +549,8 @@ pub mod mind {
         company_name: &str,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} started work at {}",
-            year, mind.first_name, mind.last_name, mind.age, company_name
+            "Year: {} - {} aged {} started work at {}",
+            year, get_full_name(mind), mind.age, company_name
         ));
         return mind;
     }
@@ -406,8 +561,8 @@ pub mod mind {
         company_name: &str,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} left company {}",
-            year, mind.first_name, mind.last_name, mind.age, company_name
+            "Year: {} - {} aged {} left company {}",
+            year, get_full_name(mind), mind.age, company_name
         ));
         return mind;
     }
@@ -418,8 +573,8 @@ pub mod mind {
         company_name: &str,
     ) -> &'a mut Mind {
         mind.activity_log.push(format!(
-            "Year: {} - {} {} aged {} created the company {}",
-            year, mind.first_name, mind.last_name, mind.age, company_name
+            "Year: {} - {} aged {} created the company {}",
+            year, get_full_name(mind), mind.age, company_name
         ));
         return mind;
     }
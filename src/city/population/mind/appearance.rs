@@ -1,6 +1,41 @@
 pub mod appearance {
+    use std::ops::Range;
+
+    use crate::city::city::City;
+    use crate::city::institutions::institutions::Institution;
+    use crate::city::population::mind::genetics::genetics::*;
+    use crate::city::population::mind::mind::{add_event_to_mind_log, find_employer, Mind};
+    use crate::city::population::mind::relations::relations::ADULT_AGE_FROM;
+    use crate::city::population::mind::retirement::retirement::retiree_wealth_tier;
+    use crate::language::nouns::nouns::NounTag;
     use crate::language::{adjectives::adjectives::AdjectiveTag, language::*};
+    use crate::utils::utils::{random_pick, thread_rng};
     use rand::Rng;
+    use uuid::Uuid;
+
+    const GREYING_AGE: u32 = 40;
+    const WHITENING_AGE: u32 = 65;
+    const HAIR_LOSS_AGE: u32 = 50;
+    const MIDDLE_AGE_SPREAD_RANGE: Range<u32> = 30..60;
+    const FRAILTY_AGE: u32 = 70;
+    const FEATURE_AGE: u32 = 18;
+    const ELDERLY_FEATURE_AGE: u32 = 55;
+
+    const HAIR_COLOUR_CHANGE_RATE: f32 = 0.01; // per year over the threshold
+    const HAIR_LOSS_RATE: f32 = 0.02;
+    const BUILD_CHANGE_RATE: f32 = 0.02;
+    const FEATURE_RATE: f32 = 0.01;
+    const ELDERLY_FEATURE_RATE: f32 = 0.04;
+    const DISTINGUISHING_MARK_CHANCE: f32 = 0.3;
+
+    const WEALTHY_COST_MULTIPLIER: f32 = 3.5;
+
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    pub enum WealthTier {
+        Poor,
+        Common,
+        Wealthy,
+    }
 
     #[derive(PartialEq, Debug, Clone)]
     pub struct PhysicalDescription {
@@ -10,6 +45,15 @@ pub mod appearance {
         pub eye_colour: String,
         pub height_adjective: String,
         pub build_adjective: String,
+        pub features: Vec<String>,
+        pub skin_tone: String,
+        pub face_shape: String,
+        pub distinguishing_marks: Vec<String>,
+        pub voice: String,
+        pub attire_adjective: String,
+        pub attire: String,
+        pub attire_plural: bool,
+        pub attire_tier: WealthTier,
     }
 
     pub fn empty_description() -> PhysicalDescription {
@@ -20,6 +64,15 @@ pub mod appearance {
             eye_colour: String::new(),
             height_adjective: String::new(),
             build_adjective: String::new(),
+            features: Vec::new(),
+            skin_tone: String::new(),
+            face_shape: String::new(),
+            distinguishing_marks: Vec::new(),
+            voice: String::new(),
+            attire_adjective: String::new(),
+            attire: String::new(),
+            attire_plural: false,
+            attire_tier: WealthTier::Common,
         };
     }
 
@@ -29,17 +82,128 @@ pub mod appearance {
             WordType::Adjective,
             &vec![target],
             &vec![],
+            &vec![AdjectiveTag::ElderlyFlag.to_string()],
+            &None,
+        )
+        .unwrap();
+    }
+
+    fn random_elderly_descriptor(dict: &Vec<Word>, target: String) -> Word {
+        return random_word_by_tag(
+            &dict,
+            WordType::Adjective,
+            &vec![target, AdjectiveTag::ElderlyFlag.to_string()],
+            &vec![],
             &vec![],
             &None,
         )
         .unwrap();
     }
 
-    pub fn random_mind_description(dict: &Vec<Word>) -> PhysicalDescription {
-        return PhysicalDescription {
-            hair_colour: random_descriptor(&dict, AdjectiveTag::HairColour.to_string())
+    // Renders a genetic trait into one of the existing adjective words for that tag
+    fn phenotype_descriptor(dict: &Vec<Word>, target: String, options: Vec<&str>) -> String {
+        let matches: Vec<&Word> = dict
+            .iter()
+            .filter(|w| {
+                w.word_type.eq(&WordType::Adjective)
+                    && w.tags.contains(&target)
+                    && options.contains(&w.text.as_str())
+            })
+            .collect();
+        if matches.len() < 1 {
+            return random_descriptor(&dict, target).text.to_lowercase();
+        }
+        return random_pick(&matches).text.to_lowercase();
+    }
+
+    fn random_distinguishing_marks(dict: &Vec<Word>) -> Vec<String> {
+        let mut rng = thread_rng();
+        let mut output: Vec<String> = Vec::new();
+        while rng.gen::<f32>() < DISTINGUISHING_MARK_CHANCE {
+            let mark = random_descriptor(&dict, AdjectiveTag::DistinguishingMark.to_string())
                 .text
-                .to_lowercase(),
+                .to_lowercase();
+            if output.contains(&mark) {
+                break;
+            }
+            output.push(mark);
+        }
+        return output;
+    }
+
+    fn random_attire(
+        dict: &Vec<Word>,
+        era: &Option<Era>,
+        tier: &WealthTier,
+    ) -> (String, String, bool) {
+        let (one_of, none_of) = match tier {
+            WealthTier::Wealthy => (vec![AdjectiveTag::Positive.to_string()], vec![]),
+            WealthTier::Poor => (vec![AdjectiveTag::Negative.to_string()], vec![]),
+            WealthTier::Common => (
+                vec![],
+                vec![
+                    AdjectiveTag::Positive.to_string(),
+                    AdjectiveTag::Negative.to_string(),
+                ],
+            ),
+        };
+        let style = filter_words_by_tag_and(
+            &dict,
+            WordType::Adjective,
+            vec![AdjectiveTag::AttireStyle.to_string()],
+        )
+        .iter()
+        .filter(|w| one_of.iter().all(|t| w.tags.contains(t)))
+        .filter(|w| !none_of.iter().any(|t| w.tags.contains(t)))
+        .map(|w| w.text.to_lowercase())
+        .collect();
+        let item = random_word_by_tag(
+            &dict,
+            WordType::Noun,
+            &vec![NounTag::Worn.to_string()],
+            &vec![],
+            &vec![],
+            era,
+        )
+        .unwrap();
+        let plural = item.tags.contains(&NounTag::Plural.to_string());
+        return (random_pick(&style), item.text.to_lowercase(), plural);
+    }
+
+    pub fn is_wealthy_employer(employer: &Institution) -> bool {
+        return employer.customer_cost_multipler > WEALTHY_COST_MULTIPLIER;
+    }
+
+    pub fn wealth_tier(mind: &Mind, city: &City) -> WealthTier {
+        if mind.age < ADULT_AGE_FROM {
+            return WealthTier::Common;
+        }
+        if mind.retired {
+            return retiree_wealth_tier(mind, city);
+        }
+        let employer = find_employer(&mind, &city);
+        if employer.is_none() {
+            return WealthTier::Poor;
+        }
+        if is_wealthy_employer(employer.unwrap()) {
+            return WealthTier::Wealthy;
+        }
+        return WealthTier::Common;
+    }
+
+    pub fn random_mind_description(
+        dict: &Vec<Word>,
+        genotype: &Genotype,
+        era: &Option<Era>,
+    ) -> PhysicalDescription {
+        let (attire_adjective, attire, attire_plural) =
+            random_attire(&dict, era, &WealthTier::Common);
+        return PhysicalDescription {
+            hair_colour: phenotype_descriptor(
+                &dict,
+                AdjectiveTag::HairColour.to_string(),
+                vec![&hair_colour_phenotype(&genotype)],
+            ),
             hair_length: random_descriptor(&dict, AdjectiveTag::HairLength.to_string())
                 .text
                 .to_lowercase(),
@@ -48,15 +212,36 @@ pub mod appearance {
                     .text
                     .to_lowercase(),
             ],
-            eye_colour: random_descriptor(&dict, AdjectiveTag::Colour.to_string())
+            eye_colour: phenotype_descriptor(
+                &dict,
+                AdjectiveTag::Colour.to_string(),
+                vec![&eye_colour_phenotype(&genotype)],
+            ),
+            height_adjective: phenotype_descriptor(
+                &dict,
+                AdjectiveTag::CreatureHeight.to_string(),
+                height_phenotype(&genotype),
+            ),
+            build_adjective: phenotype_descriptor(
+                &dict,
+                AdjectiveTag::CreatureBuild.to_string(),
+                build_phenotype(&genotype),
+            ),
+            features: Vec::new(),
+            skin_tone: random_descriptor(&dict, AdjectiveTag::SkinTone.to_string())
                 .text
                 .to_lowercase(),
-            height_adjective: random_descriptor(&dict, AdjectiveTag::CreatureHeight.to_string())
+            face_shape: random_descriptor(&dict, AdjectiveTag::FaceShape.to_string())
                 .text
                 .to_lowercase(),
-            build_adjective: random_descriptor(&dict, AdjectiveTag::CreatureBuild.to_string())
+            distinguishing_marks: random_distinguishing_marks(&dict),
+            voice: random_descriptor(&dict, AdjectiveTag::Voice.to_string())
                 .text
                 .to_lowercase(),
+            attire_adjective,
+            attire,
+            attire_plural,
+            attire_tier: WealthTier::Common,
         };
     }
 
@@ -66,7 +251,7 @@ pub mod appearance {
         a1: &String,
         a2: &String,
     ) -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let roll = rng.gen::<f32>();
         if roll < 0.45 {
             return a1.clone();
@@ -79,54 +264,251 @@ pub mod appearance {
 
     pub fn generate_child_description(
         dict: &Vec<Word>,
+        genotype: &Genotype,
+        era: &Option<Era>,
         p1: &PhysicalDescription,
         p2: &PhysicalDescription,
     ) -> PhysicalDescription {
-        return PhysicalDescription {
-            hair_colour: choose_or_mutate_attribute(
-                &dict,
-                AdjectiveTag::HairColour.to_string(),
-                &p1.hair_colour,
-                &p2.hair_colour,
-            ),
-            hair_length: choose_or_mutate_attribute(
-                &dict,
-                AdjectiveTag::HairLength.to_string(),
-                &p1.hair_length,
-                &p2.hair_length,
-            ),
-            hair_adjectives: vec![choose_or_mutate_attribute(
-                &dict,
-                AdjectiveTag::HairState.to_string(),
-                &p1.hair_adjectives.first().unwrap(),
-                &p2.hair_adjectives.first().unwrap(),
-            )],
-            eye_colour: choose_or_mutate_attribute(
+        let mut description = random_mind_description(&dict, &genotype, era);
+        // hair style isn't genetic, but children tend to be kept like their parents
+        description.hair_length = choose_or_mutate_attribute(
+            &dict,
+            AdjectiveTag::HairLength.to_string(),
+            &p1.hair_length,
+            &p2.hair_length,
+        );
+        description.hair_adjectives = vec![choose_or_mutate_attribute(
+            &dict,
+            AdjectiveTag::HairState.to_string(),
+            &p1.hair_adjectives.first().unwrap(),
+            &p2.hair_adjectives.first().unwrap(),
+        )];
+        description.skin_tone = choose_or_mutate_attribute(
+            &dict,
+            AdjectiveTag::SkinTone.to_string(),
+            &p1.skin_tone,
+            &p2.skin_tone,
+        );
+        description.face_shape = choose_or_mutate_attribute(
+            &dict,
+            AdjectiveTag::FaceShape.to_string(),
+            &p1.face_shape,
+            &p2.face_shape,
+        );
+        return description;
+    }
+
+    // Applies a single year of ageing, returning a description of each change made
+    pub fn age_description(
+        dict: &Vec<Word>,
+        description: &mut PhysicalDescription,
+        age: u32,
+    ) -> Vec<String> {
+        let mut rng = thread_rng();
+        let mut changes: Vec<String> = Vec::new();
+
+        let hair_colour_roll = rng.gen::<f32>();
+        if age > GREYING_AGE
+            && hair_colour_roll < (age - GREYING_AGE) as f32 * HAIR_COLOUR_CHANGE_RATE
+        {
+            let greyed = filter_words_by_tag_and(
                 &dict,
-                AdjectiveTag::Colour.to_string(),
-                &p1.eye_colour,
-                &p2.eye_colour,
-            ),
-            height_adjective: choose_or_mutate_attribute(
+                WordType::Adjective,
+                vec![
+                    AdjectiveTag::HairColour.to_string(),
+                    AdjectiveTag::ElderlyFlag.to_string(),
+                ],
+            )
+            .iter()
+            .any(|w| w.text.to_lowercase().eq(&description.hair_colour));
+            if !greyed {
+                description.hair_colour =
+                    phenotype_descriptor(&dict, AdjectiveTag::HairColour.to_string(), vec!["Grey"]);
+                changes.push(format!("hair turned {}", description.hair_colour));
+            } else if age > WHITENING_AGE
+                && !description.hair_colour.eq("white")
+                && hair_colour_roll < (age - WHITENING_AGE) as f32 * HAIR_COLOUR_CHANGE_RATE
+            {
+                description.hair_colour = phenotype_descriptor(
+                    &dict,
+                    AdjectiveTag::HairColour.to_string(),
+                    vec!["White"],
+                );
+                changes.push(format!("hair turned {}", description.hair_colour));
+            }
+        }
+
+        if age > HAIR_LOSS_AGE && rng.gen::<f32>() < HAIR_LOSS_RATE {
+            let thinned = filter_words_by_tag_and(
                 &dict,
-                AdjectiveTag::CreatureHeight.to_string(),
-                &p1.height_adjective,
-                &p2.height_adjective,
-            ),
-            build_adjective: choose_or_mutate_attribute(
+                WordType::Adjective,
+                vec![
+                    AdjectiveTag::HairState.to_string(),
+                    AdjectiveTag::ElderlyFlag.to_string(),
+                ],
+            )
+            .iter()
+            .any(|w| description.hair_adjectives.contains(&w.text.to_lowercase()));
+            if !thinned {
+                let state = random_elderly_descriptor(&dict, AdjectiveTag::HairState.to_string())
+                    .text
+                    .to_lowercase();
+                description.hair_adjectives = vec![state.clone()];
+                changes.push(format!("hair became {}", state));
+            }
+        }
+
+        let build_options: Vec<&str> = if MIDDLE_AGE_SPREAD_RANGE.contains(&age) {
+            vec!["Stout", "Heavy", "Broad"]
+        } else if age > FRAILTY_AGE {
+            vec!["Thin", "Delicate"]
+        } else {
+            vec![]
+        };
+        if build_options.len() > 0
+            && !build_options
+                .iter()
+                .any(|b| b.to_lowercase().eq(&description.build_adjective))
+            && rng.gen::<f32>() < BUILD_CHANGE_RATE
+        {
+            description.build_adjective = phenotype_descriptor(
                 &dict,
                 AdjectiveTag::CreatureBuild.to_string(),
-                &p1.build_adjective,
-                &p2.build_adjective,
-            ),
-        };
+                build_options,
+            );
+            changes.push(format!("build became {}", description.build_adjective));
+        }
+
+        let feature_roll = rng.gen::<f32>();
+        if age > FEATURE_AGE
+            && (feature_roll < FEATURE_RATE
+                || (age > ELDERLY_FEATURE_AGE && feature_roll < ELDERLY_FEATURE_RATE))
+        {
+            let possible_features: Vec<Word> = filter_words_by_tag_and(
+                &dict,
+                WordType::Adjective,
+                vec![AdjectiveTag::AgeingFeature.to_string()],
+            )
+            .iter()
+            .filter(|w| {
+                age > ELDERLY_FEATURE_AGE
+                    || !w.tags.contains(&AdjectiveTag::ElderlyFlag.to_string())
+            })
+            .filter(|w| !description.features.contains(&w.text.to_lowercase()))
+            .map(|w| w.clone())
+            .collect();
+            if possible_features.len() > 0 {
+                let feature = random_pick(&possible_features).text.to_lowercase();
+                description.features.push(feature.clone());
+                changes.push(format!("became {}", feature));
+            }
+        }
+        return changes;
+    }
+
+    pub fn age_appearance_per_year<'a>(city: &'a mut City, dict: &Vec<Word>) -> &'a mut City {
+        let citizen_ids: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|c| c.alive)
+            .map(|c| c.id)
+            .collect();
+        for id in citizen_ids {
+            let tier = wealth_tier(city.citizens.get(&id).unwrap(), &city);
+            let mind = city.citizens.get_mut(&id).unwrap();
+            let mut changes = age_description(&dict, &mut mind.physical_description, mind.age);
+            let description = &mut mind.physical_description;
+            if !description.attire_tier.eq(&tier) {
+                let (attire_adjective, attire, attire_plural) =
+                    random_attire(&dict, &city.culture.era, &tier);
+                description.attire_adjective = attire_adjective;
+                description.attire = attire;
+                description.attire_plural = attire_plural;
+                description.attire_tier = tier;
+                changes.push(format!("started wearing {}", describe_attire(description)));
+            }
+            for change in changes {
+                add_event_to_mind_log(mind, city.year, &change);
+            }
+        }
+        return city;
+    }
+
+    fn with_article(word: &str) -> String {
+        let vowel = word
+            .chars()
+            .next()
+            .map(|c| "aeiou".contains(c))
+            .unwrap_or(false);
+        return format!("{} {}", if vowel { "an" } else { "a" }, word);
+    }
+
+    // Plural garments such as jeans take no article
+    fn describe_attire(description: &PhysicalDescription) -> String {
+        let attire = format!("{} {}", description.attire_adjective, description.attire);
+        if description.attire_plural {
+            return attire;
+        }
+        return with_article(&attire);
+    }
+
+    fn join_list(items: &Vec<String>) -> String {
+        if items.len() < 2 {
+            return items.join("");
+        }
+        return format!(
+            "{} and {}",
+            items[0..items.len() - 1].join(", "),
+            items.last().unwrap()
+        );
+    }
+
+    pub fn describe_appearance(description: &PhysicalDescription) -> String {
+        let mut output = format!(
+            "They have {}, {} {} hair, {} eyes and {} skin. They are {} with {} build and {} face.",
+            description.hair_adjectives.first().unwrap(),
+            description.hair_length,
+            description.hair_colour,
+            description.eye_colour,
+            description.skin_tone,
+            description.height_adjective,
+            with_article(&description.build_adjective),
+            with_article(&description.face_shape),
+        );
+        let marks = vec![
+            description.distinguishing_marks.clone(),
+            description.features.clone(),
+        ]
+        .concat();
+        if marks.len() > 0 {
+            output.push_str(&format!(" They are {}.", join_list(&marks)));
+        }
+        output.push_str(&format!(
+            " They speak with {} voice and wear {}.",
+            with_article(&description.voice),
+            describe_attire(&description)
+        ));
+        return output;
+    }
+
+    #[test]
+    fn test_describe_attire() {
+        let mut description = empty_description();
+        description.attire_adjective = String::from("immaculate");
+        description.attire = String::from("jeans and jumper");
+        description.attire_plural = true;
+        assert_eq!(describe_attire(&description), "immaculate jeans and jumper");
+        description.attire = String::from("overcoat");
+        description.attire_plural = false;
+        assert_eq!(describe_attire(&description), "an immaculate overcoat");
     }
 
     #[test]
     fn random_description_test() {
         let dict = build_dictionary();
         for _i in 0..10 {
-            println!("{:#?}", random_mind_description(&dict));
+            let description = random_mind_description(&dict, &random_genotype(), &None);
+            println!("{}", describe_appearance(&description));
         }
     }
 }
@@ -0,0 +1,172 @@
+pub mod faith {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::institutions::InstituteType;
+    use crate::city::population::mind::mind::{add_event_to_mind_log, find_employer, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::culture::culture::CultureConfig;
+    use crate::culture::religion::religion::{describe_deity, find_deity, find_faith, Faith};
+    use crate::utils::utils::{random_pick, random_weighted_pick, thread_rng};
+
+    const FAITH_INHERIT_CHANCE: f32 = 0.85;
+    // Fraction of the culture's faithless rate applied as young adults decide for themselves
+    const COMING_OF_AGE_LAPSE_SCALE: f32 = 0.5;
+    const ADULT_LAPSE_RATE: f32 = 0.01;
+    const CONVERSION_RATE: f32 = 0.05;
+
+    pub fn random_faith_id(culture: &CultureConfig) -> Option<Uuid> {
+        let mut rng = thread_rng();
+        if culture.faiths.len() < 1 || rng.gen::<f32>() < culture.faithless_rate {
+            return None;
+        }
+        let weighted: Vec<(Uuid, f32)> = culture
+            .faiths
+            .iter()
+            .map(|f| (f.id.clone(), f.popularity))
+            .collect();
+        return Some(random_weighted_pick(&weighted));
+    }
+
+    // Children are raised in the faith of one of their parents, if either has one
+    pub fn inherit_faith_id(parent_1: &Mind, parent_2: &Mind) -> Option<Uuid> {
+        let mut rng = thread_rng();
+        let parent_faiths: Vec<Uuid> = vec![parent_1.faith, parent_2.faith]
+            .iter()
+            .filter(|f| f.is_some())
+            .map(|f| f.unwrap())
+            .collect();
+        if parent_faiths.len() < 1 || rng.gen::<f32>() > FAITH_INHERIT_CHANCE {
+            return None;
+        }
+        return Some(random_pick(&parent_faiths));
+    }
+
+    // Join a faith, taking on one of its deities as a patron
+    pub fn adopt_faith<'a>(mind: &'a mut Mind, faith: &Faith) -> &'a mut Mind {
+        mind.faith = Some(faith.id.clone());
+        mind.institution_worship_visits.clear();
+        mind.relations
+            .retain(|(v, _id)| !(v.eq(&RelationVerb::Diety) || v.eq(&RelationVerb::Priest)));
+        if faith.pantheon.len() > 0 {
+            let patron = random_pick(&faith.pantheon);
+            mind.relations.push((RelationVerb::Diety, patron.id));
+        }
+        return mind;
+    }
+
+    pub fn abandon_faith<'a>(mind: &'a mut Mind) -> &'a mut Mind {
+        mind.faith = None;
+        mind.institution_worship_visits.clear();
+        mind.relations
+            .retain(|(v, _id)| !(v.eq(&RelationVerb::Diety) || v.eq(&RelationVerb::Priest)));
+        return mind;
+    }
+
+    // e.g. "Order of the Sea, devoted to Lyra, Goddess of the Sea"
+    pub fn describe_faith(mind: &Mind, culture: &CultureConfig) -> String {
+        if mind.faith.is_none() {
+            return String::from("None");
+        }
+        let faith = find_faith(&culture.faiths, &mind.faith.unwrap());
+        if faith.is_none() {
+            return String::from("None");
+        }
+        let patron = mind
+            .relations
+            .iter()
+            .find(|(v, _id)| v.eq(&RelationVerb::Diety))
+            .map(|(_v, id)| find_deity(&culture.faiths, id))
+            .flatten();
+        return match patron {
+            Some(deity) => format!(
+                "{}, devoted to {}",
+                faith.unwrap().name,
+                describe_deity(deity)
+            ),
+            None => faith.unwrap().name.clone(),
+        };
+    }
+
+    fn is_priest(mind: &Mind, city: &City) -> bool {
+        let employer = find_employer(&mind, &city);
+        return employer.is_some()
+            && employer
+                .unwrap()
+                .institute_type
+                .eq(&InstituteType::PlaceOfWorship);
+    }
+
+    // A faithless person may be brought into the faith of the people closest to them
+    fn conversion_target(mind: &Mind, city: &City) -> Option<Uuid> {
+        let faiths: Vec<Uuid> = mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| {
+                vec![
+                    RelationVerb::Partner,
+                    RelationVerb::Spouse,
+                    RelationVerb::CloseFriend,
+                ]
+                .contains(v)
+            })
+            .map(|(_v, id)| city.citizens.get(id))
+            .filter(|m| m.is_some() && m.unwrap().alive && m.unwrap().faith.is_some())
+            .map(|m| m.unwrap().faith.unwrap())
+            .collect();
+        if faiths.len() < 1 {
+            return None;
+        }
+        return Some(random_pick(&faiths));
+    }
+
+    pub fn update_faith_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        let ids: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive)
+            .map(|m| m.id)
+            .collect();
+        for id in ids {
+            let mind = city.citizens.get(&id).unwrap();
+            if mind.faith.is_some() {
+                let faith = find_faith(&city.culture.faiths, &mind.faith.unwrap());
+                let devotion = if faith.is_some() {
+                    faith.unwrap().devotion
+                } else {
+                    0.0
+                };
+                let lapse_chance = if mind.age == city.culture.adult_age {
+                    city.culture.faithless_rate * COMING_OF_AGE_LAPSE_SCALE
+                } else if is_priest(mind, city) {
+                    0.0
+                } else {
+                    ADULT_LAPSE_RATE * (1.0 - devotion)
+                };
+                if faith.is_some() && rng.gen::<f32>() < lapse_chance {
+                    let faith_name = faith.unwrap().name.clone();
+                    let mind = city.citizens.get_mut(&id).unwrap();
+                    abandon_faith(mind);
+                    add_event_to_mind_log(mind, city.year, &format!("left the {}", faith_name));
+                }
+            } else if mind.age >= city.culture.adult_age && rng.gen::<f32>() < CONVERSION_RATE {
+                let target = conversion_target(mind, city);
+                if target.is_some() {
+                    let faith = find_faith(&city.culture.faiths, &target.unwrap())
+                        .unwrap()
+                        .clone();
+                    let mind = city.citizens.get_mut(&id).unwrap();
+                    adopt_faith(mind, &faith);
+                    add_event_to_mind_log(
+                        mind,
+                        city.year,
+                        &format!("converted to the {}", faith.name),
+                    );
+                }
+            }
+        }
+        return city;
+    }
+}
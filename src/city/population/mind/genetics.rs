@@ -0,0 +1,174 @@
+pub mod genetics {
+    use crate::utils::utils::thread_rng;
+    use rand::Rng;
+
+    const MUTATION_RATE: f32 = 0.01;
+
+    // Alleles are ordered from most to least dominant
+    #[derive(PartialEq, Debug, Clone, Copy, PartialOrd)]
+    pub enum HairAllele {
+        Black,
+        Brown,
+        Blonde,
+        Red,
+    }
+
+    #[derive(PartialEq, Debug, Clone, Copy, PartialOrd)]
+    pub enum EyeAllele {
+        Brown,
+        Green,
+        Blue,
+        Grey,
+    }
+
+    // Height and build are polygenic, each allele adds to the total
+    pub const HEIGHT_GENE_COUNT: usize = 2;
+    pub const BUILD_GENE_COUNT: usize = 2;
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Genotype {
+        pub hair: (HairAllele, HairAllele),
+        pub eyes: (EyeAllele, EyeAllele),
+        pub height: Vec<(bool, bool)>,
+        pub build: Vec<(bool, bool)>,
+    }
+
+    const HAIR_ALLELES: [HairAllele; 4] = [
+        HairAllele::Black,
+        HairAllele::Brown,
+        HairAllele::Blonde,
+        HairAllele::Red,
+    ];
+
+    const EYE_ALLELES: [EyeAllele; 4] = [
+        EyeAllele::Brown,
+        EyeAllele::Green,
+        EyeAllele::Blue,
+        EyeAllele::Grey,
+    ];
+
+    fn random_hair_allele() -> HairAllele {
+        let mut rng = thread_rng();
+        return HAIR_ALLELES[(rng.gen::<f32>() * HAIR_ALLELES.len() as f32) as usize];
+    }
+
+    fn random_eye_allele() -> EyeAllele {
+        let mut rng = thread_rng();
+        return EYE_ALLELES[(rng.gen::<f32>() * EYE_ALLELES.len() as f32) as usize];
+    }
+
+    fn random_polygenic_allele() -> bool {
+        let mut rng = thread_rng();
+        return rng.gen::<f32>() < 0.5;
+    }
+
+    fn random_polygenic_pairs(count: usize) -> Vec<(bool, bool)> {
+        return (0..count)
+            .map(|_i| (random_polygenic_allele(), random_polygenic_allele()))
+            .collect();
+    }
+
+    pub fn random_genotype() -> Genotype {
+        return Genotype {
+            hair: (random_hair_allele(), random_hair_allele()),
+            eyes: (random_eye_allele(), random_eye_allele()),
+            height: random_polygenic_pairs(HEIGHT_GENE_COUNT),
+            build: random_polygenic_pairs(BUILD_GENE_COUNT),
+        };
+    }
+
+    // Pass on one of the two alleles a parent carries, with a small chance of mutation
+    fn pass_on<T: Copy>(pair: &(T, T), mutate: fn() -> T) -> T {
+        let mut rng = thread_rng();
+        if rng.gen::<f32>() < MUTATION_RATE {
+            return mutate();
+        }
+        return if rng.gen::<f32>() < 0.5 {
+            pair.0
+        } else {
+            pair.1
+        };
+    }
+
+    fn pass_on_polygenic(p1: &Vec<(bool, bool)>, p2: &Vec<(bool, bool)>) -> Vec<(bool, bool)> {
+        return p1
+            .iter()
+            .zip(p2.iter())
+            .map(|(a, b)| {
+                (
+                    pass_on(a, random_polygenic_allele),
+                    pass_on(b, random_polygenic_allele),
+                )
+            })
+            .collect();
+    }
+
+    pub fn inherit_genotype(p1: &Genotype, p2: &Genotype) -> Genotype {
+        return Genotype {
+            hair: (
+                pass_on(&p1.hair, random_hair_allele),
+                pass_on(&p2.hair, random_hair_allele),
+            ),
+            eyes: (
+                pass_on(&p1.eyes, random_eye_allele),
+                pass_on(&p2.eyes, random_eye_allele),
+            ),
+            height: pass_on_polygenic(&p1.height, &p2.height),
+            build: pass_on_polygenic(&p1.build, &p2.build),
+        };
+    }
+
+    fn dominant<T: PartialOrd + Copy>(pair: &(T, T)) -> T {
+        return if pair.0 <= pair.1 { pair.0 } else { pair.1 };
+    }
+
+    fn polygenic_score(pairs: &Vec<(bool, bool)>) -> usize {
+        return pairs.iter().map(|(a, b)| *a as usize + *b as usize).sum();
+    }
+
+    pub fn hair_colour_phenotype(genotype: &Genotype) -> String {
+        return format!("{:?}", dominant(&genotype.hair));
+    }
+
+    pub fn eye_colour_phenotype(genotype: &Genotype) -> String {
+        return format!("{:?}", dominant(&genotype.eyes));
+    }
+
+    pub fn height_phenotype(genotype: &Genotype) -> Vec<&'static str> {
+        let score = polygenic_score(&genotype.height);
+        let max = HEIGHT_GENE_COUNT * 2;
+        if score * 4 <= max {
+            return vec!["Short"];
+        } else if score * 4 >= max * 3 {
+            return vec!["Tall"];
+        }
+        return vec!["Average Height"];
+    }
+
+    pub fn build_phenotype(genotype: &Genotype) -> Vec<&'static str> {
+        let score = polygenic_score(&genotype.build);
+        let max = BUILD_GENE_COUNT * 2;
+        if score * 4 <= max {
+            return vec!["Thin", "Willowy", "Delicate"];
+        } else if score * 4 >= max * 3 {
+            return vec!["Stout", "Broad", "Heavy"];
+        }
+        return vec!["Athletic"];
+    }
+
+    #[test]
+    fn test_recessive_inheritance() {
+        let mut p1 = random_genotype();
+        let mut p2 = random_genotype();
+        p1.hair = (HairAllele::Black, HairAllele::Red);
+        p2.hair = (HairAllele::Brown, HairAllele::Red);
+        assert_eq!(hair_colour_phenotype(&p1), "Black");
+        assert_eq!(hair_colour_phenotype(&p2), "Brown");
+        let red_children = (0..1000)
+            .map(|_i| inherit_genotype(&p1, &p2))
+            .filter(|g| hair_colour_phenotype(g).eq("Red"))
+            .count();
+        // expect roughly a quarter of children to express the recessive allele
+        assert!(red_children > 150 && red_children < 350);
+    }
+}
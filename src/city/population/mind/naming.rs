@@ -0,0 +1,347 @@
+pub mod naming {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::institutions::InstituteType;
+    use crate::city::population::mind::mind::{add_event_to_mind_log, find_employer, Gender, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::language::language::*;
+    use crate::language::nouns::nouns::NounTag;
+    use crate::utils::utils::{random_pick, thread_rng};
+
+    const MIDDLE_NAME_CHANCE: f32 = 0.6;
+    const SECOND_MIDDLE_NAME_CHANCE: f32 = 0.15;
+    const HONOUR_GRANDPARENT_CHANCE: f32 = 0.4;
+
+    const NICKNAME_AGE_RANGE: std::ops::Range<u32> = 6..30;
+    const NICKNAME_RATE: f32 = 0.03;
+    const TRAIT_NICKNAME_CHANCE: f32 = 0.4;
+
+    const NOBILITY_RATE: f32 = 0.01;
+    const ACADEMIC_TITLE_MIN_AGE: u32 = 25;
+    const ACADEMIC_TITLE_RATE: f32 = 0.05;
+    const HONORIFIC_MIN_AGE: u32 = 45;
+    const HONORIFIC_RATE: f32 = 0.005;
+
+    // Male and female forms of the hereditary titles in static_data/nouns/titles.csv
+    const NOBLE_TITLE_PAIRS: [(&str, &str); 3] =
+        [("Lord", "Lady"), ("Baron", "Baroness"), ("Duke", "Duchess")];
+
+    // Description words that can earn someone a nickname
+    const TRAIT_NICKNAMES: [(&str, &str); 10] = [
+        ("red", "Red"),
+        ("blonde", "Goldie"),
+        ("tall", "Lofty"),
+        ("short", "Shorty"),
+        ("heavy", "Tank"),
+        ("broad", "Ox"),
+        ("willowy", "Twig"),
+        ("bespectacled", "Specs"),
+        ("booming", "Boomer"),
+        ("freckled", "Freckles"),
+    ];
+
+    pub fn random_first_name(dict: &Vec<Word>, gender: &Gender, era: &Option<Era>) -> String {
+        let mut first_name_tags = if gender.eq(&Gender::Ambiguous) {
+            vec![String::from("FirstName")]
+        } else {
+            vec![String::from("FirstName"), format!("Gender{}", gender)]
+        };
+        if era.is_some() {
+            first_name_tags.push(era.unwrap().to_string());
+        }
+        return random_word_by_tag_and(&dict, WordType::Noun, first_name_tags)
+            .unwrap()
+            .text
+            .clone();
+    }
+
+    pub fn random_middle_names(
+        dict: &Vec<Word>,
+        gender: &Gender,
+        era: &Option<Era>,
+        honoured_names: &Vec<String>,
+    ) -> Vec<String> {
+        let mut rng = thread_rng();
+        let mut output: Vec<String> = Vec::new();
+        if rng.gen::<f32>() > MIDDLE_NAME_CHANCE {
+            return output;
+        }
+        let count = if rng.gen::<f32>() < SECOND_MIDDLE_NAME_CHANCE {
+            2
+        } else {
+            1
+        };
+        for _i in 0..count {
+            let name = if honoured_names.len() > 0 && rng.gen::<f32>() < HONOUR_GRANDPARENT_CHANCE {
+                random_pick(honoured_names)
+            } else {
+                random_first_name(&dict, gender, era)
+            };
+            if !output.contains(&name) {
+                output.push(name);
+            }
+        }
+        return output;
+    }
+
+    // First names of a child's grandparents, preferring those of the child's gender
+    pub fn grandparent_names(
+        gender: &Gender,
+        parent_1: &Mind,
+        parent_2: &Mind,
+        city: &City,
+    ) -> Vec<String> {
+        let grandparents: Vec<&Mind> = vec![parent_1, parent_2]
+            .iter()
+            .flat_map(|p| {
+                p.relations
+                    .iter()
+                    .filter(|(v, _id)| v.eq(&RelationVerb::Parent))
+                    .map(|(_v, id)| city.citizens.get(id))
+                    .filter(|m| m.is_some())
+                    .map(|m| m.unwrap())
+            })
+            .collect();
+        let same_gender: Vec<String> = grandparents
+            .iter()
+            .filter(|m| m.gender.eq(gender))
+            .map(|m| m.first_name.clone())
+            .collect();
+        if same_gender.len() > 0 {
+            return same_gender;
+        }
+        return grandparents.iter().map(|m| m.first_name.clone()).collect();
+    }
+
+    fn is_vowel(c: char) -> bool {
+        return "aeiouyAEIOUY".contains(c);
+    }
+
+    // Shortens a first name to its leading syllable, e.g. Jonathan -> Jon / Jonny
+    fn diminutive(first_name: &str) -> Option<String> {
+        let mut rng = thread_rng();
+        let chars: Vec<char> = first_name.chars().collect();
+        if chars.len() < 5 || !chars.iter().all(|c| c.is_alphabetic()) {
+            return None;
+        }
+        let first_vowel = chars.iter().position(|c| is_vowel(*c))?;
+        let syllable_end = chars
+            .iter()
+            .enumerate()
+            .skip(first_vowel + 1)
+            .find(|(_i, c)| !is_vowel(**c))
+            .map(|(i, _c)| i)?;
+        if syllable_end + 1 >= chars.len() {
+            return None;
+        }
+        let short: String = chars[0..=syllable_end].iter().collect();
+        if rng.gen::<f32>() < 0.5 {
+            return Some(short);
+        }
+        let last = chars[syllable_end].to_lowercase().to_string();
+        return Some(format!(
+            "{}{}{}",
+            short,
+            last,
+            if rng.gen::<f32>() < 0.5 { "y" } else { "ie" }
+        ));
+    }
+
+    fn trait_nickname(mind: &Mind) -> Option<String> {
+        let description = &mind.physical_description;
+        let traits: Vec<&String> = vec![
+            vec![
+                &description.hair_colour,
+                &description.height_adjective,
+                &description.build_adjective,
+                &description.voice,
+                &description.skin_tone,
+            ],
+            description.features.iter().collect(),
+            description.distinguishing_marks.iter().collect(),
+        ]
+        .concat();
+        let options: Vec<String> = TRAIT_NICKNAMES
+            .iter()
+            .filter(|(word, _nickname)| traits.iter().any(|t| t.eq(word)))
+            .map(|(_word, nickname)| String::from(*nickname))
+            .collect();
+        if options.len() < 1 {
+            return None;
+        }
+        return Some(random_pick(&options));
+    }
+
+    pub fn random_nickname(mind: &Mind) -> Option<String> {
+        let mut rng = thread_rng();
+        if rng.gen::<f32>() < TRAIT_NICKNAME_CHANCE {
+            let nickname = trait_nickname(&mind);
+            if nickname.is_some() {
+                return nickname;
+            }
+        }
+        return diminutive(&mind.first_name);
+    }
+
+    fn random_title(
+        dict: &Vec<Word>,
+        era: &Option<Era>,
+        category: NounTag,
+        gender: &Gender,
+    ) -> Option<String> {
+        let excluded_genders: Vec<String> = match gender {
+            Gender::Male => vec![NounTag::GenderFemale.to_string()],
+            Gender::Female => vec![NounTag::GenderMale.to_string()],
+            Gender::Ambiguous => vec![],
+        };
+        let eras: Vec<String> = vec![
+            Era::Modern.to_string(),
+            Era::Future.to_string(),
+            Era::Fantasy.to_string(),
+            Era::Medieval.to_string(),
+        ];
+        let options: Vec<Word> = filter_words_by_tag_and(
+            &dict,
+            WordType::Noun,
+            vec![NounTag::Title.to_string(), category.to_string()],
+        )
+        .iter()
+        .filter(|w| !excluded_genders.iter().any(|t| w.tags.contains(t)))
+        .filter(|w| {
+            era.is_none()
+                || w.tags.contains(&era.unwrap().to_string())
+                || !eras.iter().any(|e| w.tags.contains(e))
+        })
+        .map(|w| w.clone())
+        .collect();
+        if options.len() < 1 {
+            return None;
+        }
+        return Some(random_pick(&options).text);
+    }
+
+    pub fn random_noble_title(
+        dict: &Vec<Word>,
+        era: &Option<Era>,
+        gender: &Gender,
+    ) -> Option<String> {
+        let mut rng = thread_rng();
+        if rng.gen::<f32>() > NOBILITY_RATE {
+            return None;
+        }
+        return random_title(&dict, era, NounTag::Nobility, gender);
+    }
+
+    fn is_hereditary(title: &str) -> bool {
+        return NOBLE_TITLE_PAIRS
+            .iter()
+            .any(|(m, f)| m.eq(&title) || f.eq(&title));
+    }
+
+    fn hereditary_title_for(title: &str, gender: &Gender) -> String {
+        let pair = NOBLE_TITLE_PAIRS
+            .iter()
+            .find(|(m, f)| m.eq(&title) || f.eq(&title));
+        return match (pair, gender) {
+            (Some((m, _f)), Gender::Male) => String::from(*m),
+            (Some((_m, f)), Gender::Female) => String::from(*f),
+            _ => String::from(title),
+        };
+    }
+
+    pub fn get_full_name(mind: &Mind) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if mind.title.is_some() {
+            parts.push(mind.title.clone().unwrap());
+        }
+        parts.push(mind.first_name.clone());
+        for middle_name in &mind.middle_names {
+            parts.push(middle_name.clone());
+        }
+        if mind.nickname.is_some() {
+            parts.push(format!("\"{}\"", mind.nickname.clone().unwrap()));
+        }
+        parts.push(mind.last_name.clone());
+        return parts.join(" ");
+    }
+
+    pub fn inherit_titles<'a>(city: &'a mut City, dead_ids: &Vec<Uuid>) -> &'a mut City {
+        for dead_id in dead_ids {
+            let deceased = city.citizens.get(dead_id).unwrap().clone();
+            if deceased.title.is_none() || !is_hereditary(&deceased.title.clone().unwrap()) {
+                continue;
+            }
+            let mut heirs: Vec<&Mind> = deceased
+                .relations
+                .iter()
+                .filter(|(v, _id)| v.eq(&RelationVerb::Child))
+                .map(|(_v, id)| city.citizens.get(id))
+                .filter(|m| m.is_some() && m.unwrap().alive)
+                .map(|m| m.unwrap())
+                .collect();
+            heirs.sort_by(|a, b| b.age.cmp(&a.age));
+            let heir_id = heirs.first().map(|m| m.id);
+            if heir_id.is_some() {
+                let heir = city.citizens.get_mut(&heir_id.unwrap()).unwrap();
+                let title = hereditary_title_for(&deceased.title.clone().unwrap(), &heir.gender);
+                heir.title = Some(title.clone());
+                add_event_to_mind_log(heir, city.year, &format!("inherited the title {}", title));
+            }
+        }
+        return city;
+    }
+
+    pub fn update_names_per_year<'a>(city: &'a mut City, dict: &Vec<Word>) -> &'a mut City {
+        let mut rng = thread_rng();
+        let citizen_ids: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|c| c.alive)
+            .map(|c| c.id)
+            .collect();
+        for id in citizen_ids {
+            let mind_ref = city.citizens.get(&id).unwrap();
+            let employer_type = find_employer(&mind_ref, &city).map(|i| i.institute_type.clone());
+            let mind = city.citizens.get_mut(&id).unwrap();
+
+            if mind.nickname.is_none()
+                && NICKNAME_AGE_RANGE.contains(&mind.age)
+                && rng.gen::<f32>() < NICKNAME_RATE
+            {
+                mind.nickname = random_nickname(&mind);
+                if mind.nickname.is_some() {
+                    let change = format!("became known as \"{}\"", mind.nickname.clone().unwrap());
+                    add_event_to_mind_log(mind, city.year, &change);
+                }
+            }
+
+            if mind.title.is_none() && employer_type.is_some() {
+                let title = match employer_type.unwrap() {
+                    InstituteType::Hospital | InstituteType::University
+                        if mind.age > ACADEMIC_TITLE_MIN_AGE
+                            && rng.gen::<f32>() < ACADEMIC_TITLE_RATE =>
+                    {
+                        random_title(&dict, &city.culture.era, NounTag::Academic, &mind.gender)
+                    }
+                    InstituteType::CityHall | InstituteType::Court
+                        if mind.age > HONORIFIC_MIN_AGE && rng.gen::<f32>() < HONORIFIC_RATE =>
+                    {
+                        random_title(&dict, &city.culture.era, NounTag::Honorific, &mind.gender)
+                    }
+                    _ => None,
+                };
+                if title.is_some() {
+                    mind.title = title.clone();
+                    add_event_to_mind_log(
+                        mind,
+                        city.year,
+                        &format!("earned the title {}", title.unwrap()),
+                    );
+                }
+            }
+        }
+        return city;
+    }
+}
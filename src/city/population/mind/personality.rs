@@ -0,0 +1,57 @@
+pub mod personality {
+    use crate::utils::utils::thread_rng;
+    use rand::Rng;
+
+    const INHERITED_TRAIT_VARIANCE: f32 = 0.3;
+
+    // Each trait runs from 0 to 1
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Personality {
+        pub honesty: f32,
+        pub temper: f32,
+    }
+
+    fn random_trait() -> f32 {
+        let mut rng = thread_rng();
+        // averaging two rolls clusters most people around the middle
+        return (rng.gen::<f32>() + rng.gen::<f32>()) / 2.0;
+    }
+
+    pub fn random_personality() -> Personality {
+        return Personality {
+            honesty: random_trait(),
+            temper: random_trait(),
+        };
+    }
+
+    fn inherit_trait(p1: f32, p2: f32) -> f32 {
+        let mut rng = thread_rng();
+        let variance = (rng.gen::<f32>() - 0.5) * 2.0 * INHERITED_TRAIT_VARIANCE;
+        return ((p1 + p2) / 2.0 + variance).clamp(0.0, 1.0);
+    }
+
+    pub fn inherit_personality(p1: &Personality, p2: &Personality) -> Personality {
+        return Personality {
+            honesty: inherit_trait(p1.honesty, p2.honesty),
+            temper: inherit_trait(p1.temper, p2.temper),
+        };
+    }
+
+    pub fn describe_personality(personality: &Personality) -> String {
+        let honesty = if personality.honesty < 0.3 {
+            "Deceitful"
+        } else if personality.honesty > 0.7 {
+            "Honest"
+        } else {
+            "Pragmatic"
+        };
+        let temper = if personality.temper < 0.3 {
+            "placid"
+        } else if personality.temper > 0.7 {
+            "hot-headed"
+        } else {
+            "even-tempered"
+        };
+        return format!("{} and {}", honesty, temper);
+    }
+}
@@ -1,11 +1,21 @@
+pub mod adoption;
+pub mod affinity;
+pub mod custody;
+pub mod family_by_marriage;
 pub mod friends;
+pub mod grudges;
+pub mod housing_market;
+pub mod kinship;
+pub mod marriage;
 pub mod parental_naming_formats;
 pub mod parents;
 pub mod partners;
 pub mod residences;
+pub mod widowhood;
 pub mod relations {
     use crate::city::city::City;
     use crate::city::population::{mind::mind::*, population::Population};
+    use crate::utils::utils::thread_rng;
     // use rand::seq::SliceRandom;
     use rand::Rng;
     use strum_macros::Display;
@@ -30,15 +40,26 @@ pub mod relations {
         Cousin,
         Pibling, // Aunt/Uncle
         Nibling, // Neice/Nephew
+        ParentInLaw,
+        ChildInLaw,
+        SiblingInLaw,
+        StepParent,
+        StepChild,
+        StepSibling,
         // business
         Employer,
         Employee,
         Colleague,
+        Landlord,
+        Tenant,
         // social
         Acquaintance,
         Friend,
         CloseFriend,
         Grudge,
+        Neighbour,
+        // lives on another floor of the same building
+        BuildingNeighbour,
         // religion
         Diety,
         Priest,
@@ -47,7 +68,7 @@ pub mod relations {
     pub const ADULT_AGE_FROM: u32 = 18;
 
     pub fn invert_gender(gender: &Gender) -> Gender {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         if gender.eq(&Gender::Male) {
             return Gender::Female;
         }
@@ -209,21 +230,57 @@ pub mod relations {
             RelationVerb::Pibling => Some(RelationVerb::Nibling),
             RelationVerb::Grandparent => Some(RelationVerb::Grandchild),
             RelationVerb::Grandchild => Some(RelationVerb::Grandparent),
+            RelationVerb::ParentInLaw => Some(RelationVerb::ChildInLaw),
+            RelationVerb::ChildInLaw => Some(RelationVerb::ParentInLaw),
+            RelationVerb::SiblingInLaw => Some(RelationVerb::SiblingInLaw),
+            RelationVerb::StepParent => Some(RelationVerb::StepChild),
+            RelationVerb::StepChild => Some(RelationVerb::StepParent),
+            RelationVerb::StepSibling => Some(RelationVerb::StepSibling),
             _ => None,
         };
     }
 
+    // Ties that already make someone a child's kin, so they are not linked again as other kin
+    const KIN_VERBS: [RelationVerb; 13] = [
+        RelationVerb::Parent,
+        RelationVerb::Child,
+        RelationVerb::AdoptedParent,
+        RelationVerb::AdoptedChild,
+        RelationVerb::Sibling,
+        RelationVerb::Grandparent,
+        RelationVerb::Grandchild,
+        RelationVerb::Cousin,
+        RelationVerb::Pibling,
+        RelationVerb::Nibling,
+        RelationVerb::StepParent,
+        RelationVerb::StepChild,
+        RelationVerb::StepSibling,
+    ];
+
+    fn is_kin(mind: &Mind, id: &Uuid) -> bool {
+        return mind
+            .relations
+            .iter()
+            .any(|(v, kin_id)| kin_id.eq(id) && KIN_VERBS.contains(v));
+    }
+
+    // Also used on adoption, where the child may already be kin to the adoptive family
     pub fn link_family_at_birth<'a>(city: &'a mut City, child: &'a mut Mind) -> &'a mut City {
         let parents: Vec<Mind> = child
             .relations
             .iter()
-            .filter(|(v, _id)| v.eq(&RelationVerb::Parent))
+            .filter(|(v, _id)| {
+                v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent)
+            })
             .map(|(_v, id)| city.citizens.get(&id).unwrap().clone())
             .collect();
         for parent in parents {
             for (verb, id) in parent.relations.iter().filter(|(_v, id)| !id.eq(&child.id)) {
+                if is_kin(child, id) {
+                    continue;
+                }
                 match verb {
-                    RelationVerb::Child => {
+                    RelationVerb::Child | RelationVerb::AdoptedChild => {
                         // Create Siblings
                         if !child
                             .relations
@@ -273,7 +330,9 @@ pub mod relations {
                                 .iter()
                                 .find(|(v, _id)| v.eq(&RelationVerb::Spouse));
 
-                            if pibling_spouse_option.is_some() {
+                            if pibling_spouse_option.is_some()
+                                && !is_kin(child, &pibling_spouse_option.unwrap().1)
+                            {
                                 let pibling_spouse = city
                                     .citizens
                                     .get_mut(&pibling_spouse_option.unwrap().1)
@@ -296,11 +355,15 @@ pub mod relations {
                         }
                         // println!("Add Pibling");
                     }
-                    RelationVerb::Parent => {
+                    RelationVerb::Parent | RelationVerb::AdoptedParent => {
                         // Create Grandparent
 
                         let grandparent = city.citizens.get_mut(&id).unwrap();
-                        if grandparent.alive {
+                        if grandparent.alive
+                            && !child
+                                .relations
+                                .contains(&(RelationVerb::Grandparent, id.clone()))
+                        {
                             child
                                 .relations
                                 .push((RelationVerb::Grandparent, id.clone()));
@@ -0,0 +1,290 @@
+pub mod adoption {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::justice::justice::is_imprisoned;
+    use crate::city::population::household::household::{join_household, leave_household};
+    use crate::city::population::mind::faith::faith::{adopt_faith, inherit_faith_id};
+    use crate::city::population::mind::mind::{
+        add_adoption_to_mind_log, add_new_relation_to_mind_log, random_char, Mind,
+    };
+    use crate::city::population::mind::relations::parental_naming_formats::parental_naming_formats::get_child_last_name;
+    use crate::city::population::mind::relations::parents::parents::find_couples;
+    use crate::city::population::mind::relations::partners::partners::TAKEN_VERBS;
+    use crate::city::population::mind::relations::relations::{
+        link_family_at_birth, RelationVerb,
+    };
+    use crate::culture::religion::religion::find_faith;
+    use crate::language::language::Word;
+    use crate::utils::utils::thread_rng;
+
+    // Adopters must be at least this much older than the child they take in
+    const MIN_ADOPTER_AGE_GAP: u32 = 18;
+    const ADOPTIVE_CHILD_LIMIT: usize = 3;
+    // Annual chance a couple unable to have children of their own adopts from outside the city
+    const FOUNDLING_ADOPTION_CHANCE: f32 = 0.05;
+    const FOUNDLING_MAX_AGE: f32 = 6.0;
+    const KIN_GUARDIAN_VERBS: [RelationVerb; 2] =
+        [RelationVerb::Pibling, RelationVerb::Grandparent];
+
+    pub fn living_guardians(mind: &Mind, city: &City) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, id)| {
+                (v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+                    && city.citizens.get(id).is_some_and(|m| m.alive)
+            })
+            .map(|(_v, id)| id.clone())
+            .collect();
+    }
+
+    fn is_orphan(mind: &Mind, city: &City) -> bool {
+        return mind.alive
+            && mind.age < city.culture.adult_age
+            && living_guardians(mind, city).len() < 1;
+    }
+
+    fn can_adopt(mind: &Mind, child_age: u32, city: &City) -> bool {
+        return mind.alive
+            && mind.age >= city.culture.adult_age
+            && mind.age >= child_age + MIN_ADOPTER_AGE_GAP
+            && !is_imprisoned(mind);
+    }
+
+    fn child_count(mind: &Mind) -> usize {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Child) || v.eq(&RelationVerb::AdoptedChild))
+            .count();
+    }
+
+    pub fn cannot_bear(m1: &Mind, m2: &Mind) -> bool {
+        return m1.gender.eq(&m2.gender) || !m1.fertile || !m2.fertile;
+    }
+
+    // A relative takes the child in along with their partner, if they have one
+    fn kin_household(relative: &Mind, child: &Mind, city: &City) -> Option<Vec<Uuid>> {
+        if !can_adopt(relative, child.age, city) {
+            return None;
+        }
+        let partner = relative
+            .relations
+            .iter()
+            .find(|(v, _id)| TAKEN_VERBS.contains(v))
+            .map(|(_v, id)| city.citizens.get(id).unwrap())
+            .filter(|p| p.alive && p.age >= city.culture.adult_age && !is_imprisoned(p));
+        return match partner {
+            Some(p) => Some(vec![relative.id.clone(), p.id.clone()]),
+            None => Some(vec![relative.id.clone()]),
+        };
+    }
+
+    fn find_kin_guardians(child: &Mind, city: &City) -> Option<Vec<Uuid>> {
+        let mut rng = thread_rng();
+        for verb in KIN_GUARDIAN_VERBS.iter() {
+            let mut households: Vec<Vec<Uuid>> = child
+                .relations
+                .iter()
+                .filter(|(v, _id)| v.eq(verb))
+                .filter_map(|(_v, id)| kin_household(city.citizens.get(id).unwrap(), child, city))
+                .collect();
+            households.shuffle(&mut rng);
+            // couples are preferred over single relatives
+            households.sort_by_key(|h| usize::MAX - h.len());
+            if households.len() > 0 {
+                return Some(households[0].clone());
+            }
+        }
+        return None;
+    }
+
+    // Couples open to adoption, with those unable to have children of their own first in line
+    fn adoptive_couples(city: &City) -> Vec<(Uuid, Uuid)> {
+        let mut rng = thread_rng();
+        let mut couples: Vec<(Uuid, Uuid, bool)> = find_couples(&city.citizens)
+            .iter()
+            .filter(|(m1, m2)| {
+                can_adopt(m1, 0, city)
+                    && can_adopt(m2, 0, city)
+                    && child_count(m1) + child_count(m2) < ADOPTIVE_CHILD_LIMIT * 2
+            })
+            .map(|(m1, m2)| (m1.id.clone(), m2.id.clone(), cannot_bear(m1, m2)))
+            .collect();
+        couples.shuffle(&mut rng);
+        couples.sort_by_key(|(_m1, _m2, childless)| !childless);
+        return couples.iter().map(|(m1, m2, _c)| (*m1, *m2)).collect();
+    }
+
+    fn adopt(city: &mut City, child_id: &Uuid, adopter_ids: &Vec<Uuid>) {
+        let year = city.year;
+        let mut child = city.citizens.remove(child_id).unwrap();
+        let adopters: Vec<Mind> = adopter_ids
+            .iter()
+            .map(|id| city.citizens.get(id).unwrap().clone())
+            .collect();
+        for adopter in adopters.iter() {
+            child
+                .relations
+                .push((RelationVerb::AdoptedParent, adopter.id.clone()));
+            let adopter = city.citizens.get_mut(&adopter.id).unwrap();
+            adopter
+                .relations
+                .push((RelationVerb::AdoptedChild, child.id.clone()));
+            add_new_relation_to_mind_log(adopter, year, RelationVerb::AdoptedChild, &child);
+        }
+        add_adoption_to_mind_log(&mut child, year, &adopters);
+        link_family_at_birth(city, &mut child);
+        city.citizens.insert(child.id.clone(), child);
+        // the child moves in with their new family
+        let household = adopters.iter().find_map(|a| a.household);
+        match household {
+            Some(household_id) => join_household(city, &household_id, child_id),
+            None => leave_household(city, child_id),
+        }
+    }
+
+    pub fn adopt_orphans_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let orphans: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| is_orphan(m, city))
+            .map(|m| m.id)
+            .collect();
+        let mut couples = adoptive_couples(city);
+        for orphan_id in orphans {
+            let orphan = city.citizens.get(&orphan_id).unwrap();
+            let kin = find_kin_guardians(orphan, city);
+            if kin.is_some() {
+                adopt(city, &orphan_id, &kin.unwrap());
+                continue;
+            }
+            let age = orphan.age;
+            let couple_index = couples.iter().position(|(m1, m2)| {
+                can_adopt(city.citizens.get(m1).unwrap(), age, city)
+                    && can_adopt(city.citizens.get(m2).unwrap(), age, city)
+            });
+            if couple_index.is_some() {
+                let (m1, m2) = couples.remove(couple_index.unwrap());
+                adopt(city, &orphan_id, &vec![m1, m2]);
+            }
+        }
+        return city;
+    }
+
+    // Couples who cannot have children together may bring one in from outside the city
+    pub fn adopt_foundlings_per_year<'a>(city: &'a mut City, dict: &Vec<Word>) -> &'a mut City {
+        let mut rng = thread_rng();
+        let couples: Vec<(Mind, Mind)> = adoptive_couples(city)
+            .iter()
+            .map(|(m1, m2)| {
+                (
+                    city.citizens.get(m1).unwrap().clone(),
+                    city.citizens.get(m2).unwrap().clone(),
+                )
+            })
+            .filter(|(m1, m2)| cannot_bear(m1, m2))
+            .collect();
+        for (m1, m2) in couples {
+            if rng.gen::<f32>() > FOUNDLING_ADOPTION_CHANCE {
+                continue;
+            }
+            let mut child = random_char(&dict, &city.culture, false);
+            child.age = (rng.gen::<f32>() * FOUNDLING_MAX_AGE) as u32;
+            child.last_name = get_child_last_name(&child.gender, &m1, &m2, &city.culture);
+            let faith_id = inherit_faith_id(&m1, &m2);
+            if faith_id.is_some() {
+                let faith = find_faith(&city.culture.faiths, &faith_id.unwrap());
+                adopt_faith(&mut child, faith.unwrap());
+            }
+            let child_id = child.id.clone();
+            city.citizens.insert(child_id.clone(), child);
+            adopt(city, &child_id, &vec![m1.id.clone(), m2.id.clone()]);
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_adoption_links_adoptive_family() {
+        use crate::city::city::new_city;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut city = new_city(String::new(), &random_culture(&dict, &None));
+        // each citizen is born in turn, picking up the family their parents already have
+        let born = |city: &mut City, parent_ids: Vec<Uuid>| -> Uuid {
+            let mut mind = random_char(&dict, &city.culture, true);
+            mind.relations.clear();
+            mind.age = 10;
+            for parent_id in parent_ids.iter() {
+                mind.relations
+                    .push((RelationVerb::Parent, parent_id.clone()));
+                let parent = city.citizens.get_mut(parent_id).unwrap();
+                parent
+                    .relations
+                    .push((RelationVerb::Child, mind.id.clone()));
+                parent.age = 60;
+            }
+            link_family_at_birth(city, &mut mind);
+            let id = mind.id.clone();
+            city.citizens.insert(id.clone(), mind);
+            return id;
+        };
+        let grandmother = born(&mut city, vec![]);
+        let grandfather = born(&mut city, vec![]);
+        let mother = born(&mut city, vec![grandmother, grandfather]);
+        let uncle = born(&mut city, vec![grandmother, grandfather]);
+        let cousin = born(&mut city, vec![uncle]);
+        let orphan = born(&mut city, vec![mother]);
+        let elder = born(&mut city, vec![]);
+        let adopter = born(&mut city, vec![elder]);
+        let adopters_sister = born(&mut city, vec![elder]);
+        let adopters_niece = born(&mut city, vec![adopters_sister]);
+        let adopters_son = born(&mut city, vec![adopter]);
+        let foundling = born(&mut city, vec![]);
+        let verbs = |city: &City, id: &Uuid, other_id: &Uuid| -> Vec<RelationVerb> {
+            return city
+                .citizens
+                .get(id)
+                .unwrap()
+                .relations
+                .iter()
+                .filter(|(_v, rel_id)| rel_id.eq(other_id))
+                .map(|(v, _id)| v.clone())
+                .collect();
+        };
+
+        // kin who take a child in are already family, so nothing is linked twice
+        city.citizens.get_mut(&mother).unwrap().alive = false;
+        adopt(&mut city, &orphan, &vec![grandmother, grandfather]);
+        assert_eq!(
+            verbs(&city, &orphan, &grandmother),
+            vec![RelationVerb::Grandparent, RelationVerb::AdoptedParent]
+        );
+        assert_eq!(verbs(&city, &orphan, &uncle), vec![RelationVerb::Pibling]);
+        assert_eq!(verbs(&city, &orphan, &cousin), vec![RelationVerb::Cousin]);
+
+        // a stranger's family becomes the child's own
+        adopt(&mut city, &foundling, &vec![adopter]);
+        assert_eq!(
+            verbs(&city, &foundling, &adopters_son),
+            vec![RelationVerb::Sibling]
+        );
+        assert_eq!(
+            verbs(&city, &foundling, &elder),
+            vec![RelationVerb::Grandparent]
+        );
+        assert_eq!(
+            verbs(&city, &foundling, &adopters_sister),
+            vec![RelationVerb::Pibling]
+        );
+        assert_eq!(
+            verbs(&city, &foundling, &adopters_niece),
+            vec![RelationVerb::Cousin]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+pub mod affinity {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::visits::visits::{get_habitual_institutions, VisitType};
+    use crate::city::population::mind::mind::Mind;
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::thread_rng;
+
+    pub const AFFINITY_MAX: i32 = 100;
+    pub const AFFINITY_MIN: i32 = -100;
+
+    // Score boundaries between social verbs, from worst to best
+    const GRUDGE_THRESHOLD: i32 = -40;
+    const FORGOTTEN_SCORE: i32 = 0;
+    const FRIEND_THRESHOLD: i32 = 35;
+    const CLOSE_FRIEND_THRESHOLD: i32 = 70;
+
+    const SHARED_SOCIAL_PLACE_CONTACT: f32 = 1.0;
+    const SHARED_WORKPLACE_CONTACT: f32 = 1.5;
+    const SHARED_HOME_CONTACT: f32 = 2.0;
+    const NEIGHBOUR_CONTACT: f32 = 1.0;
+    const BUILDING_NEIGHBOUR_CONTACT: f32 = 0.3;
+    const CONTACT_AFFINITY_SCALE: f32 = 8.0;
+    const TIME_APART_DECAY: i32 = 6;
+    const COMPATIBLE_AGE_GAP: f32 = 10.0;
+
+    // A starting point for ties that have never been scored
+    pub fn default_affinity(verb: &RelationVerb) -> i32 {
+        return match verb {
+            RelationVerb::Acquaintance => 10,
+            RelationVerb::Friend => 50,
+            RelationVerb::CloseFriend => 80,
+            RelationVerb::Grudge => -50,
+            RelationVerb::Partner => 65,
+            RelationVerb::Spouse => 75,
+            _ => 0,
+        };
+    }
+
+    pub fn get_affinity(mind: &Mind, verb: &RelationVerb, other_id: &Uuid) -> i32 {
+        return match mind.affinities.get(other_id) {
+            Some(score) => *score,
+            None => default_affinity(verb),
+        };
+    }
+
+    pub fn set_affinity(city: &mut City, id_1: &Uuid, id_2: &Uuid, score: i32) {
+        let score = score.clamp(AFFINITY_MIN, AFFINITY_MAX);
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let mind = city.citizens.get_mut(id);
+            if mind.is_some() {
+                mind.unwrap().affinities.insert(other.clone(), score);
+            }
+        }
+    }
+
+    pub fn clear_affinity(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let mind = city.citizens.get_mut(id);
+            if mind.is_some() {
+                mind.unwrap().affinities.remove(other);
+            }
+        }
+    }
+
+    // The social verb a tie settles into for a given score, None meaning the two drift apart entirely.
+    // Time apart eases a score back to zero but never softens a grudge, so only contact can end one.
+    pub fn social_verb_for_affinity(score: i32) -> Option<RelationVerb> {
+        if score <= GRUDGE_THRESHOLD {
+            return Some(RelationVerb::Grudge);
+        } else if score == FORGOTTEN_SCORE {
+            return None;
+        } else if score < FRIEND_THRESHOLD {
+            return Some(RelationVerb::Acquaintance);
+        } else if score < CLOSE_FRIEND_THRESHOLD {
+            return Some(RelationVerb::Friend);
+        }
+        return Some(RelationVerb::CloseFriend);
+    }
+
+    pub fn are_neighbours(mind: &Mind, other_id: &Uuid) -> bool {
+        return mind
+            .relations
+            .iter()
+            .any(|(v, id)| v.eq(&RelationVerb::Neighbour) && id.eq(other_id));
+    }
+
+    // How much time two people spend in each other's company over a year
+    fn shared_contact(m1: &Mind, m2: &Mind) -> f32 {
+        let (social_1, _) = get_habitual_institutions(m1, &VisitType::Social);
+        let (social_2, _) = get_habitual_institutions(m2, &VisitType::Social);
+        let shared_social = social_1.iter().filter(|id| social_2.contains(id)).count();
+        let mut contact = shared_social as f32 * SHARED_SOCIAL_PLACE_CONTACT;
+        if m1.employer.is_some() && m1.employer.eq(&m2.employer) {
+            contact += SHARED_WORKPLACE_CONTACT;
+        }
+        if m1.residence.is_some() && m1.residence.eq(&m2.residence) {
+            contact += SHARED_HOME_CONTACT;
+        }
+        if are_neighbours(m1, &m2.id) {
+            contact += NEIGHBOUR_CONTACT;
+        } else if m1
+            .relations
+            .iter()
+            .any(|(v, id)| v.eq(&RelationVerb::BuildingNeighbour) && id.eq(&m2.id))
+        {
+            contact += BUILDING_NEIGHBOUR_CONTACT;
+        }
+        return contact;
+    }
+
+    // Between -1 and 1, a fixed spark (or clash) between two particular people
+    fn chemistry(m1: &Mind, m2: &Mind) -> f32 {
+        let seed = (m1.id.as_u128() ^ m2.id.as_u128()) % 2001;
+        return seed as f32 / 1000.0 - 1.0;
+    }
+
+    // Between -1 and 1, how well two people tend to get on when they do meet
+    fn compatibility(m1: &Mind, m2: &Mind) -> f32 {
+        let age_gap = (m1.age as f32 - m2.age as f32).abs();
+        let age_fit = (1.0 - (age_gap / COMPATIBLE_AGE_GAP)).max(-1.0);
+        return (age_fit * 0.3 + chemistry(m1, m2) * 0.7).clamp(-1.0, 1.0);
+    }
+
+    fn affinity_change(m1: &Mind, m2: &Mind, current: i32) -> i32 {
+        let mut rng = thread_rng();
+        let contact = shared_contact(m1, m2);
+        if contact <= 0.0 {
+            // time apart pulls most feelings back towards indifference, but a grudge is nursed
+            return if current > 0 {
+                -TIME_APART_DECAY.min(current)
+            } else if current > GRUDGE_THRESHOLD {
+                (TIME_APART_DECAY / 2).min(-current)
+            } else {
+                0
+            };
+        }
+        let mood = rng.gen::<f32>() * 2.0 - 1.0;
+        let change = compatibility(m1, m2) * 0.75 + mood * 0.25;
+        return (contact * change * CONTACT_AFFINITY_SCALE).round() as i32;
+    }
+
+    // Scales the yearly chance of a couple splitting, happier couples being less likely to part
+    pub fn romantic_split_modifier(score: i32, verb: &RelationVerb) -> f32 {
+        let baseline = default_affinity(verb);
+        return (1.0 + (baseline - score) as f32 / 50.0).clamp(0.25, 3.0);
+    }
+
+    // Apply a year of shared (or lack of) contact to a tie and return the new score
+    pub fn update_affinity(city: &mut City, verb: &RelationVerb, id_1: &Uuid, id_2: &Uuid) -> i32 {
+        let m1 = city.citizens.get(id_1).unwrap();
+        let m2 = city.citizens.get(id_2).unwrap();
+        let current = get_affinity(m1, verb, id_2);
+        let score = current + affinity_change(m1, m2, current);
+        set_affinity(city, id_1, id_2, score);
+        return score.clamp(AFFINITY_MIN, AFFINITY_MAX);
+    }
+
+    #[test]
+    fn test_social_verb_thresholds() {
+        assert_eq!(social_verb_for_affinity(-80), Some(RelationVerb::Grudge));
+        assert_eq!(social_verb_for_affinity(0), None);
+        assert_eq!(
+            social_verb_for_affinity(-10),
+            Some(RelationVerb::Acquaintance)
+        );
+        assert_eq!(
+            social_verb_for_affinity(default_affinity(&RelationVerb::Acquaintance)),
+            Some(RelationVerb::Acquaintance)
+        );
+        assert_eq!(
+            social_verb_for_affinity(default_affinity(&RelationVerb::Friend)),
+            Some(RelationVerb::Friend)
+        );
+        assert_eq!(
+            social_verb_for_affinity(AFFINITY_MAX),
+            Some(RelationVerb::CloseFriend)
+        );
+    }
+
+    #[test]
+    fn test_grudges_persist_apart() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let culture = random_culture(&dict, &None);
+        let strangers: Vec<Mind> = (0..2)
+            .map(|_i| {
+                let mut mind = random_char(&dict, &culture, true);
+                mind.relations.clear();
+                mind.employer = None;
+                mind.residence = None;
+                return mind;
+            })
+            .collect();
+        let (m1, m2) = (&strangers[0], &strangers[1]);
+        let (mut grudge, mut dislike, mut friendship) = (GRUDGE_THRESHOLD, -10, 50);
+        for _year in 0..30 {
+            grudge += affinity_change(m1, m2, grudge);
+            dislike += affinity_change(m1, m2, dislike);
+            friendship += affinity_change(m1, m2, friendship);
+            assert_eq!(social_verb_for_affinity(grudge), Some(RelationVerb::Grudge));
+        }
+        // milder feelings fade once the two stop seeing each other
+        assert_eq!(social_verb_for_affinity(dislike), None);
+        assert_eq!(social_verb_for_affinity(friendship), None);
+    }
+}
@@ -0,0 +1,255 @@
+pub mod custody {
+    use rand::Rng;
+    use strum_macros::Display;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::institutions::justice::justice::is_imprisoned;
+    use crate::city::population::household::household::{
+        find_household, household_surplus, join_household,
+    };
+    use crate::city::population::mind::mind::{add_custody_to_mind_log, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::thread_rng;
+
+    // Arrangement between a child and each of their separated parents
+    #[derive(PartialEq, Debug, Clone, Display)]
+    pub enum Custody {
+        Custodial,
+        Visitation,
+    }
+
+    fn minor_children(mind: &Mind, city: &City) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Child) || v.eq(&RelationVerb::AdoptedChild))
+            .map(|(_v, id)| city.citizens.get(id).unwrap())
+            .filter(|c| c.alive && c.age < city.culture.adult_age)
+            .map(|c| c.id.clone())
+            .collect();
+    }
+
+    // A parent with a home and money to spare is better placed to raise the children
+    fn custody_claim(parent: &Mind, city: &City) -> i32 {
+        if !parent.alive || is_imprisoned(parent) {
+            return i32::MIN;
+        }
+        let household = find_household(parent, city);
+        if household.is_none() {
+            return 0;
+        }
+        let household = household.unwrap();
+        let housed = if household.dwelling.is_some() { 2 } else { 0 };
+        let solvent = if household_surplus(household, city) > 0 {
+            1
+        } else {
+            0
+        };
+        return housed + solvent;
+    }
+
+    fn move_in_with(city: &mut City, child_id: &Uuid, parent_id: &Uuid) {
+        let household = city.citizens.get(parent_id).unwrap().household;
+        if household.is_some()
+            && !city
+                .citizens
+                .get(child_id)
+                .unwrap()
+                .household
+                .eq(&household)
+        {
+            join_household(city, &household.unwrap(), child_id);
+        }
+    }
+
+    // Decides which parent the minor children of a separating couple live with
+    pub fn settle_custody(
+        city: &mut City,
+        id_1: &Uuid,
+        id_2: &Uuid,
+        shared_household: Option<Uuid>,
+    ) {
+        let mut rng = thread_rng();
+        let parent_1 = city.citizens.get(id_1).unwrap();
+        let parent_2 = city.citizens.get(id_2).unwrap();
+        let children_1 = minor_children(parent_1, city);
+        let children_2 = minor_children(parent_2, city);
+        let (claim_1, claim_2) = (custody_claim(parent_1, city), custody_claim(parent_2, city));
+        let (custodian, visitor) =
+            if claim_1 > claim_2 || (claim_1 == claim_2 && rng.gen::<f32>() < 0.5) {
+                (parent_1.clone(), parent_2.clone())
+            } else {
+                (parent_2.clone(), parent_1.clone())
+            };
+        // with both parents dead or in prison nobody can take them, and orphans are left for adoption
+        let unclaimed = claim_1 == i32::MIN && claim_2 == i32::MIN;
+        let year = city.year;
+        for child_id in children_1
+            .iter()
+            .filter(|id| !unclaimed && children_2.contains(id))
+        {
+            move_in_with(city, child_id, &custodian.id);
+            let child = city.citizens.get_mut(child_id).unwrap();
+            child
+                .custody
+                .insert(custodian.id.clone(), Custody::Custodial);
+            child
+                .custody
+                .insert(visitor.id.clone(), Custody::Visitation);
+            add_custody_to_mind_log(child, year, &custodian, &visitor);
+            city.citizens
+                .get_mut(&custodian.id)
+                .unwrap()
+                .custody
+                .insert(child_id.clone(), Custody::Custodial);
+            city.citizens
+                .get_mut(&visitor.id)
+                .unwrap()
+                .custody
+                .insert(child_id.clone(), Custody::Visitation);
+        }
+        // children from earlier relationships who lived with the couple go with their own parent,
+        // unless another parent already has custody of them
+        if shared_household.is_none() {
+            return;
+        }
+        for (parent_id, children, others) in [
+            (id_1, &children_1, &children_2),
+            (id_2, &children_2, &children_1),
+        ] {
+            for child_id in children.iter().filter(|id| !others.contains(id)) {
+                let parent = city.citizens.get(parent_id).unwrap();
+                let child = city.citizens.get(child_id).unwrap();
+                let in_others_custody = child
+                    .custody
+                    .iter()
+                    .any(|(id, c)| !id.eq(parent_id) && c.eq(&Custody::Custodial));
+                if parent.alive
+                    && !is_imprisoned(parent)
+                    && child.household.eq(&shared_household)
+                    && !in_others_custody
+                {
+                    move_in_with(city, child_id, parent_id);
+                }
+            }
+        }
+    }
+
+    // Custody arrangements lapse once a child comes of age
+    pub fn expire_custody<'a>(city: &'a mut City) -> &'a mut City {
+        let adult_age = city.culture.adult_age;
+        let grown: Vec<(Uuid, Vec<Uuid>)> = city
+            .citizens
+            .values()
+            .filter(|c| c.custody.len() > 0 && c.age >= adult_age)
+            .map(|c| {
+                let parent_ids = c
+                    .relations
+                    .iter()
+                    .filter(|(v, id)| {
+                        (v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+                            && c.custody.contains_key(id)
+                    })
+                    .map(|(_v, id)| id.clone())
+                    .collect();
+                (c.id.clone(), parent_ids)
+            })
+            .collect();
+        for (child_id, parent_ids) in grown {
+            for parent_id in parent_ids {
+                city.citizens
+                    .get_mut(&child_id)
+                    .unwrap()
+                    .custody
+                    .remove(&parent_id);
+                city.citizens
+                    .get_mut(&parent_id)
+                    .unwrap()
+                    .custody
+                    .remove(&child_id);
+            }
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_settle_custody() {
+        use crate::city::city::new_city;
+        use crate::city::population::household::household::{found_household, set_dwelling};
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut city = new_city(String::new(), &random_culture(&dict, &None));
+        let mut add_citizen = |city: &mut City, age: u32| {
+            let mut mind = random_char(&dict, &city.culture, true);
+            mind.relations.clear();
+            mind.age = age;
+            let id = mind.id.clone();
+            city.citizens.insert(id.clone(), mind);
+            return id;
+        };
+        let (mother, father) = (add_citizen(&mut city, 35), add_citizen(&mut city, 35));
+        let shared = add_citizen(&mut city, 5);
+        let hers = add_citizen(&mut city, 8);
+        let his = add_citizen(&mut city, 9);
+        for (parent, child) in [
+            (mother, shared),
+            (father, shared),
+            (mother, hers),
+            (father, his),
+        ] {
+            city.citizens
+                .get_mut(&parent)
+                .unwrap()
+                .relations
+                .push((RelationVerb::Child, child));
+            city.citizens
+                .get_mut(&child)
+                .unwrap()
+                .relations
+                .push((RelationVerb::Parent, parent));
+        }
+        let home = found_household(&mut city, &mother);
+        for id in [father, shared, hers, his] {
+            join_household(&mut city, &home, &id);
+        }
+
+        // without a shared home only the couple's own children are settled
+        let father_home = found_household(&mut city, &father);
+        set_dwelling(&mut city, &father_home, Some(Uuid::default()));
+        settle_custody(&mut city, &mother, &father, None);
+        let child = city.citizens.get(&shared).unwrap();
+        assert_eq!(child.household, Some(father_home));
+        assert_eq!(child.custody.get(&father), Some(&Custody::Custodial));
+        assert_eq!(child.custody.get(&mother), Some(&Custody::Visitation));
+        assert_eq!(
+            city.citizens.get(&mother).unwrap().custody.get(&shared),
+            Some(&Custody::Visitation)
+        );
+        assert_eq!(city.citizens.get(&his).unwrap().household, Some(home));
+        assert!(city.citizens.get(&his).unwrap().custody.is_empty());
+
+        // children from earlier relationships leave the shared home with their own parent
+        settle_custody(&mut city, &mother, &father, Some(home));
+        assert_eq!(
+            city.citizens.get(&his).unwrap().household,
+            Some(father_home)
+        );
+        assert_eq!(city.citizens.get(&hers).unwrap().household, Some(home));
+
+        // nobody gains custody while both parents are in prison
+        for id in [mother, father, shared] {
+            city.citizens.get_mut(&id).unwrap().custody.clear();
+        }
+        join_household(&mut city, &home, &shared);
+        for id in [mother, father] {
+            city.citizens.get_mut(&id).unwrap().imprisoned_until = Some(1);
+        }
+        settle_custody(&mut city, &mother, &father, Some(home));
+        assert!(city.citizens.get(&shared).unwrap().custody.is_empty());
+        assert_eq!(city.citizens.get(&shared).unwrap().household, Some(home));
+    }
+}
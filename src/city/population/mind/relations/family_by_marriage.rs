@@ -0,0 +1,292 @@
+pub mod family_by_marriage {
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::mind::mind::{add_new_relation_to_mind_log, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::city::population::population::Population;
+    use crate::utils::utils::HashSet;
+
+    // Relations that only exist through a marriage, rebuilt whenever one starts or ends
+    pub const MARRIAGE_VERBS: [RelationVerb; 6] = [
+        RelationVerb::ParentInLaw,
+        RelationVerb::ChildInLaw,
+        RelationVerb::SiblingInLaw,
+        RelationVerb::StepParent,
+        RelationVerb::StepChild,
+        RelationVerb::StepSibling,
+    ];
+
+    fn related_by(mind: &Mind, verbs: &[RelationVerb]) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| verbs.contains(v))
+            .map(|(_v, id)| id.clone())
+            .collect();
+    }
+
+    fn parents(mind: &Mind) -> Vec<Uuid> {
+        return related_by(mind, &[RelationVerb::Parent, RelationVerb::AdoptedParent]);
+    }
+
+    fn children(mind: &Mind) -> Vec<Uuid> {
+        return related_by(mind, &[RelationVerb::Child, RelationVerb::AdoptedChild]);
+    }
+
+    fn siblings(mind: &Mind) -> Vec<Uuid> {
+        return related_by(mind, &[RelationVerb::Sibling]);
+    }
+
+    // The current spouse, or the most recent late spouse for a widow who has not remarried
+    fn spouses(mind: &Mind) -> Vec<Uuid> {
+        let current = related_by(mind, &[RelationVerb::Spouse]);
+        if current.len() > 0 {
+            return current;
+        }
+        let last_marriage = mind
+            .relations
+            .iter()
+            .rev()
+            .find(|(v, _id)| v.eq(&RelationVerb::LateSpouse) || v.eq(&RelationVerb::ExSpouse));
+        return match last_marriage {
+            Some((RelationVerb::LateSpouse, id)) => vec![id.clone()],
+            _ => Vec::new(),
+        };
+    }
+
+    fn is_alive(id: &Uuid, population: &Population) -> bool {
+        return population.get(id).map(|m| m.alive).unwrap_or(false);
+    }
+
+    // Every in-law and step relation the citizen should hold, worked out from parent, sibling and spouse links
+    pub fn family_by_marriage(mind: &Mind, population: &Population) -> Vec<(RelationVerb, Uuid)> {
+        let mut found: Vec<(RelationVerb, Uuid)> = Vec::new();
+        let add = |verb: RelationVerb, id: Uuid, found: &mut Vec<(RelationVerb, Uuid)>| {
+            if !id.eq(&mind.id)
+                && is_alive(&id, population)
+                && !found.iter().any(|(_v, f)| f.eq(&id))
+            {
+                found.push((verb, id));
+            }
+        };
+        let own_parents = parents(mind);
+        let own_siblings = siblings(mind);
+        let blood: HashSet<Uuid> = vec![own_parents.clone(), own_siblings.clone(), children(mind)]
+            .concat()
+            .into_iter()
+            .collect();
+
+        for spouse_id in spouses(mind) {
+            let spouse = population.get(&spouse_id);
+            if spouse.is_none() {
+                continue;
+            }
+            let spouse = spouse.unwrap();
+            for id in parents(spouse) {
+                add(RelationVerb::ParentInLaw, id, &mut found);
+            }
+            for id in siblings(spouse) {
+                add(RelationVerb::SiblingInLaw, id, &mut found);
+            }
+            // the spouse's children by someone else
+            for id in children(spouse)
+                .into_iter()
+                .filter(|id| !blood.contains(id))
+            {
+                add(RelationVerb::StepChild, id, &mut found);
+            }
+        }
+        for id in own_siblings.iter() {
+            let sibling = population.get(id);
+            if sibling.is_some() {
+                for spouse_id in spouses(sibling.unwrap()) {
+                    add(RelationVerb::SiblingInLaw, spouse_id, &mut found);
+                }
+            }
+        }
+        for id in children(mind) {
+            let child = population.get(&id);
+            if child.is_some() {
+                for spouse_id in spouses(child.unwrap()) {
+                    add(RelationVerb::ChildInLaw, spouse_id, &mut found);
+                }
+            }
+        }
+        for parent_id in own_parents.iter() {
+            let parent = population.get(parent_id);
+            if parent.is_none() {
+                continue;
+            }
+            for step_parent_id in spouses(parent.unwrap())
+                .into_iter()
+                .filter(|id| !own_parents.contains(id))
+            {
+                add(RelationVerb::StepParent, step_parent_id, &mut found);
+                let step_parent = population.get(&step_parent_id);
+                if step_parent.is_some() {
+                    for id in children(step_parent.unwrap())
+                        .into_iter()
+                        .filter(|id| !blood.contains(id))
+                    {
+                        add(RelationVerb::StepSibling, id, &mut found);
+                    }
+                }
+            }
+        }
+        return found;
+    }
+
+    // Citizens whose in-laws or step-family may change when this citizen marries or separates
+    fn affected_by_marriage(id: &Uuid, population: &Population) -> Vec<Uuid> {
+        let mind = population.get(id);
+        if mind.is_none() {
+            return Vec::new();
+        }
+        let mind = mind.unwrap();
+        let mut affected = vec![vec![id.clone()], parents(mind), children(mind)].concat();
+        for sibling_id in siblings(mind) {
+            affected.push(sibling_id.clone());
+            let sibling = population.get(&sibling_id);
+            if sibling.is_some() {
+                affected.append(&mut spouses(sibling.unwrap()));
+            }
+        }
+        return affected;
+    }
+
+    // Rebuilds in-law and step relations around a couple after they marry, separate or one remarries
+    pub fn update_family_by_marriage<'a>(city: &'a mut City, couple: &[Uuid]) -> &'a mut City {
+        let mut affected: Vec<Uuid> = Vec::new();
+        for id in couple {
+            for other in affected_by_marriage(id, &city.citizens) {
+                if !affected.contains(&other) && is_alive(&other, &city.citizens) {
+                    affected.push(other);
+                }
+            }
+        }
+        let updates: Vec<(Uuid, Vec<(RelationVerb, Uuid)>)> = affected
+            .iter()
+            .map(|id| {
+                let mind = city.citizens.get(id).unwrap();
+                (id.clone(), family_by_marriage(mind, &city.citizens))
+            })
+            .collect();
+        for (id, relations) in updates {
+            let gained: Vec<(RelationVerb, Mind)> = relations
+                .iter()
+                .filter(|r| !city.citizens.get(&id).unwrap().relations.contains(r))
+                .map(|(v, rid)| (v.clone(), city.citizens.get(rid).unwrap().clone()))
+                .collect();
+            let mind = city.citizens.get_mut(&id).unwrap();
+            mind.relations
+                .retain(|(v, _id)| !MARRIAGE_VERBS.contains(v));
+            mind.relations.extend(relations);
+            for (verb, relation) in gained {
+                add_new_relation_to_mind_log(mind, city.year, verb, &relation);
+            }
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_family_by_marriage() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let culture = random_culture(&dict, &None);
+        let mut population: Population = Population::default();
+        let mut add = |parent_ids: Vec<Uuid>, population: &mut Population| -> Uuid {
+            let mut mind = random_char(&dict, &culture, true);
+            mind.relations.clear();
+            for parent_id in parent_ids {
+                mind.relations.push((RelationVerb::Parent, parent_id));
+                population
+                    .get_mut(&parent_id)
+                    .unwrap()
+                    .relations
+                    .push((RelationVerb::Child, mind.id.clone()));
+            }
+            let id = mind.id.clone();
+            population.insert(id.clone(), mind);
+            return id;
+        };
+        let link = |population: &mut Population, a: &Uuid, b: &Uuid, verb: RelationVerb| {
+            for (id, other) in [(a, b), (b, a)] {
+                let mind = population.get_mut(id).unwrap();
+                mind.relations.retain(|(_v, rid)| !rid.eq(other));
+                mind.relations.push((verb.clone(), other.clone()));
+            }
+        };
+        let mother = add(vec![], &mut population);
+        let father = add(vec![], &mut population);
+        let anna = add(vec![mother, father], &mut population);
+        let sister = add(vec![mother, father], &mut population);
+        link(&mut population, &anna, &sister, RelationVerb::Sibling);
+        let annas_child = add(vec![anna], &mut population);
+        let ben = add(vec![], &mut population);
+        let bens_child = add(vec![ben], &mut population);
+        let has = |population: &Population, id: &Uuid, verb: RelationVerb, other: &Uuid| {
+            family_by_marriage(population.get(id).unwrap(), population)
+                .contains(&(verb, other.clone()))
+        };
+
+        link(&mut population, &anna, &ben, RelationVerb::Spouse);
+        assert!(has(&population, &ben, RelationVerb::ParentInLaw, &mother));
+        assert!(has(&population, &ben, RelationVerb::SiblingInLaw, &sister));
+        assert!(has(&population, &sister, RelationVerb::SiblingInLaw, &ben));
+        assert!(has(&population, &father, RelationVerb::ChildInLaw, &ben));
+        assert!(has(
+            &population,
+            &anna,
+            RelationVerb::StepChild,
+            &bens_child
+        ));
+        assert!(has(
+            &population,
+            &bens_child,
+            RelationVerb::StepParent,
+            &anna
+        ));
+        assert!(has(
+            &population,
+            &bens_child,
+            RelationVerb::StepSibling,
+            &annas_child
+        ));
+
+        // divorce ends every relation that came with the marriage
+        link(&mut population, &anna, &ben, RelationVerb::ExSpouse);
+        for id in [ben, bens_child, annas_child, sister, mother] {
+            assert_eq!(
+                family_by_marriage(population.get(&id).unwrap(), &population),
+                vec![]
+            );
+        }
+
+        // remarrying brings only the new spouse's family, and a widower keeps his in-laws
+        let cara_mother = add(vec![], &mut population);
+        let cara = add(vec![cara_mother], &mut population);
+        link(&mut population, &ben, &cara, RelationVerb::Spouse);
+        assert_eq!(
+            family_by_marriage(population.get(&ben).unwrap(), &population),
+            vec![(RelationVerb::ParentInLaw, cara_mother)]
+        );
+        population.get_mut(&cara).unwrap().alive = false;
+        link(&mut population, &ben, &cara, RelationVerb::LateSpouse);
+        assert!(has(
+            &population,
+            &ben,
+            RelationVerb::ParentInLaw,
+            &cara_mother
+        ));
+        assert!(!has(
+            &population,
+            &bens_child,
+            RelationVerb::StepParent,
+            &cara
+        ));
+    }
+}
@@ -1,12 +1,18 @@
 pub mod friends {
-    use std::collections::HashMap;
-
     use crate::city::city::City;
-    use crate::city::institutions::visits::visits::get_habitual_institutions;
+    use crate::city::institutions::institutions::InstituteType;
+    use crate::city::institutions::visits::visits::{get_habitual_institutions, VisitType};
     use crate::city::population::mind::mind::*;
+    use crate::city::population::mind::relations::affinity::affinity::{
+        are_neighbours, clear_affinity, social_verb_for_affinity, update_affinity,
+    };
+    use crate::city::population::mind::relations::grudges::grudges::{
+        clear_grudge_cause, describe_grudge_cause, set_grudge_cause, GrudgeCause,
+    };
     use crate::city::population::mind::relations::relations::RelationVerb;
-    use crate::city::population::population::Population;
+    use crate::city::population::mind::relations::residences::residences::NEIGHBOUR_VERBS;
     use crate::culture::culture::CultureConfig;
+    use crate::utils::utils::{thread_rng, HashMap};
     use rand::seq::SliceRandom;
     use rand::Rng;
     use uuid::Uuid;
@@ -17,311 +23,283 @@ pub mod friends {
         RelationVerb::CloseFriend,
     ];
 
-    const ACQUAINTANCE_DECAY_CHANCE: f32 = 0.6;
-    const ACQUAINTANCE_UPGRADE_CHANCE: f32 = 0.25;
-    const FRIEND_DECAY_CHANCE: f32 = 0.25;
-    const FRIEND_UPGRADE_CHANCE: f32 = 0.125;
-    const CLOSE_FRIEND_DECAY_CHANCE: f32 = 0.125;
+    // Social ties whose verb follows their affinity score
+    const EVOLVING_RELATIONS: [RelationVerb; 4] = [
+        RelationVerb::Acquaintance,
+        RelationVerb::Friend,
+        RelationVerb::CloseFriend,
+        RelationVerb::Grudge,
+    ];
 
     const FRIEND_OUTGOING_MAX: f32 = 20.0;
     const FRIEND_MULTIPLIER_SAME_GENDER: f32 = 0.66;
     const FRIEND_MULTIPLER_DIFFERENT_GENDER: f32 = 0.33;
     const FRIEND_RATE: f32 = 0.5;
 
-    type AgeCache<'a> = HashMap<u32, Vec<&'a Mind>>;
+    // Most candidates a citizen weighs up each year, however busy their haunts
+    const FRIEND_CANDIDATE_LIMIT: usize = 40;
+    // Children befriend those within a few years of their own age
+    const CHILD_FRIEND_AGE_GAP: u32 = 3;
+    const ADULT_FRIEND_AGE_GAP: u32 = 30;
+    const SCHOOL_START_AGE: u32 = 5;
 
-    fn process_age_cache<'a>(
-        population: &'a AgeCache,
-        cache: &'a mut (AgeCache<'a>, AgeCache<'a>),
-        target_age: u32,
-        mind: &Mind,
-    ) -> &'a mut (AgeCache<'a>, AgeCache<'a>) {
-        if !(cache.0.contains_key(&target_age) && cache.1.contains_key(&target_age))
-            && population.contains_key(&target_age)
-        {
-            let mut to_add_0: Vec<&'a Mind> = Vec::new();
-            let mut to_add_1: Vec<&'a Mind> = Vec::new();
-            let source = population.get(&target_age).unwrap();
-            for m in source {
-                if !m.id.eq(&mind.id)
-                    && m.relations.len() < FRIEND_OUTGOING_MAX as usize
-                    && !mind.relations.iter().any(|(_v, id)| id.eq(&m.id))
-                    && !m.relations.iter().any(|(_v, id)| id.eq(&mind.id))
-                {
-                    if m.gender.eq(&mind.gender) {
-                        to_add_0.push(m.clone());
-                    } else {
-                        to_add_1.push(m.clone());
-                    }
-                }
-            }
-            cache.0.insert(target_age, to_add_0);
-            cache.1.insert(target_age, to_add_1);
-        }
-        return cache;
+    // The places and groups through which citizens meet one another
+    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
+    enum SocialContext {
+        Institution(Uuid),
+        Workplace(Uuid),
+        Building(Uuid),
+        SchoolYear(u32),
     }
 
-    fn get_friend<'a>(
+    fn social_contexts(
         mind: &Mind,
-        age_population: &'a AgeCache,
-        friend_cache: &'a mut (AgeCache<'a>, AgeCache<'a>),
+        area_buildings: &HashMap<Uuid, Uuid>,
+        has_school: bool,
         culture: &CultureConfig,
-        pending_friends: &Vec<Uuid>,
-    ) -> (Option<Uuid>, &'a mut (AgeCache<'a>, AgeCache<'a>)) {
-        let mut cache = friend_cache;
-        let mut rng = rand::thread_rng();
-        let mut years_above: u32;
-        let mut years_below: u32;
-        let max_deviation = if mind.age < culture.adult_age { 3 } else { 30 };
-        let ages_processed: Vec<u32> = Vec::new();
-        // limit children to a max deviation of 3yrs in each direction
-        for i in 0..40 {
-            years_above = i.min(max_deviation);
-            years_below = i.min(max_deviation);
-            let target_above = mind.age + years_above;
-            let target_below =
-                (mind.age as i32 - years_below as i32).min(if mind.age > culture.adult_age {
-                    culture.adult_age as i32
-                } else {
-                    0
-                }) as u32;
-            cache = process_age_cache(&age_population, cache, target_above, &mind);
-            cache = process_age_cache(&age_population, cache, target_below, &mind);
-            let mut buffer_same_gender_above: &Vec<&Mind> = &Vec::new();
-            let mut buffer_same_gender_below: &Vec<&Mind> = &Vec::new();
-            let mut buffer_different_gender_above: &Vec<&Mind> = &Vec::new();
-            let mut buffer_different_gender_below: &Vec<&Mind> = &Vec::new();
+    ) -> Vec<SocialContext> {
+        let (shopping_locs, _) = get_habitual_institutions(mind, &VisitType::Shopping);
+        let (social_locs, _) = get_habitual_institutions(mind, &VisitType::Social);
+        let mut contexts: Vec<SocialContext> = vec![shopping_locs, social_locs]
+            .concat()
+            .iter()
+            .map(|id| SocialContext::Institution(**id))
+            .collect();
+        if mind.employer.is_some() {
+            contexts.push(SocialContext::Workplace(mind.employer.unwrap()));
+        }
+        let building = mind.residence.map(|r| area_buildings.get(&r)).flatten();
+        if building.is_some() {
+            contexts.push(SocialContext::Building(*building.unwrap()));
+        }
+        if has_school && mind.age >= SCHOOL_START_AGE && mind.age < culture.adult_age {
+            contexts.push(SocialContext::SchoolYear(mind.age));
+        }
+        return contexts;
+    }
 
-            if cache.0.contains_key(&target_above)
-                && cache.1.contains_key(&target_above)
-                && !ages_processed.contains(&years_above)
-            {
-                buffer_same_gender_above = cache.0.get(&target_above).unwrap();
-                buffer_different_gender_above = cache.1.get(&target_above).unwrap();
-            }
-            if cache.0.contains_key(&target_below)
-                && cache.1.contains_key(&target_below)
-                && !ages_processed.contains(&target_below)
-            {
-                buffer_same_gender_below = cache.0.get(&target_below).unwrap();
-                buffer_different_gender_below = cache.1.get(&target_below).unwrap();
+    fn hash_population_by_context(
+        city: &City,
+    ) -> (
+        HashMap<SocialContext, Vec<Uuid>>,
+        HashMap<Uuid, Vec<SocialContext>>,
+    ) {
+        let area_buildings: HashMap<Uuid, Uuid> = city
+            .buildings
+            .values()
+            .flat_map(|b| {
+                b.floors
+                    .iter()
+                    .flat_map(move |f| f.areas.iter().map(move |a| (a.id.clone(), b.id.clone())))
+            })
+            .collect();
+        let has_school = city
+            .institutions
+            .values()
+            .any(|i| i.institute_type.eq(&InstituteType::School));
+        let mut members: HashMap<SocialContext, Vec<Uuid>> = HashMap::default();
+        let mut contexts: HashMap<Uuid, Vec<SocialContext>> = HashMap::default();
+        for mind in city.citizens.values().filter(|m| m.alive) {
+            let mind_contexts = social_contexts(mind, &area_buildings, has_school, &city.culture);
+            for context in mind_contexts.iter() {
+                members
+                    .entry(context.clone())
+                    .or_insert(Vec::new())
+                    .push(mind.id.clone());
             }
+            contexts.insert(mind.id.clone(), mind_contexts);
+        }
+        return (members, contexts);
+    }
 
-            let same_gender_target_roll = (buffer_same_gender_above.len()
-                + buffer_same_gender_below.len()) as f32
-                * FRIEND_MULTIPLIER_SAME_GENDER
-                * FRIEND_RATE;
-            let different_gender_target_roll =
-                (buffer_different_gender_above.len() + buffer_different_gender_below.len()) as f32
-                    * FRIEND_MULTIPLER_DIFFERENT_GENDER
-                    * FRIEND_RATE;
+    fn ages_compatible(m1: &Mind, m2: &Mind, culture: &CultureConfig) -> bool {
+        let m1_child = m1.age < culture.adult_age;
+        let m2_child = m2.age < culture.adult_age;
+        if m1_child != m2_child {
+            return false;
+        }
+        let max_gap = if m1_child {
+            CHILD_FRIEND_AGE_GAP
+        } else {
+            ADULT_FRIEND_AGE_GAP
+        };
+        return m1.age.abs_diff(m2.age) <= max_gap;
+    }
 
-            let roll = rng.gen::<f32>();
-            let mut working_buffers: (&Vec<&Mind>, &Vec<&Mind>) = (&Vec::new(), &Vec::new());
-            if roll < same_gender_target_roll {
-                working_buffers = (buffer_same_gender_below, buffer_same_gender_above);
-            } else if roll < same_gender_target_roll + different_gender_target_roll {
-                working_buffers = (buffer_different_gender_below, buffer_different_gender_above);
+    fn is_friend_candidate(mind: &Mind, other: &Mind, culture: &CultureConfig) -> bool {
+        return !mind.id.eq(&other.id)
+            && other.alive
+            && other
+                .relations
+                .iter()
+                .filter(|(v, _id)| !NEIGHBOUR_VERBS.contains(v))
+                .count()
+                < FRIEND_OUTGOING_MAX as usize
+            && ages_compatible(mind, other, culture)
+            && !mind
+                .relations
+                .iter()
+                .any(|(v, id)| id.eq(&other.id) && !NEIGHBOUR_VERBS.contains(v));
+    }
+
+    // Draws a bounded number of people met through the citizen's contexts, keeping those who hit it off
+    fn find_new_acquaintances(
+        mind: &Mind,
+        wanted: usize,
+        city: &City,
+        members: &HashMap<SocialContext, Vec<Uuid>>,
+        contexts: &Vec<SocialContext>,
+    ) -> Vec<Uuid> {
+        let mut rng = thread_rng();
+        let mut found: Vec<Uuid> = Vec::new();
+        let neighbours: Vec<Uuid> = mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Neighbour))
+            .map(|(_v, id)| id.clone())
+            .collect();
+        // neighbours are met as often as the people at any one other haunt
+        let pool_count = contexts.len() + if neighbours.len() > 0 { 1 } else { 0 };
+        if pool_count < 1 {
+            return found;
+        }
+        for _i in 0..FRIEND_CANDIDATE_LIMIT.min(wanted * 2) {
+            if found.len() >= wanted {
+                break;
             }
-            let buffer_choice_limit = working_buffers.0.len() as f32
-                / (working_buffers.0.len() + working_buffers.1.len()) as f32;
-            let buffer_choice = rng.gen::<f32>() < buffer_choice_limit;
-            let mut target_buffer: Vec<&Mind> = if buffer_choice {
-                working_buffers.0.clone()
-            } else {
-                working_buffers.1.clone()
-            };
-            target_buffer.shuffle(&mut rng);
-            let r = target_buffer.first();
-            if r.is_some() && !pending_friends.iter().any(|m| r.unwrap().id.eq(&m)) {
-                let rtn = r.unwrap();
-                cache
-                    .0
-                    .get_mut(&rtn.age)
+            let pool = rng.gen_range(0..pool_count);
+            let candidate_id = if pool < contexts.len() {
+                members
+                    .get(&contexts[pool])
                     .unwrap()
-                    .retain(|m| !m.id.eq(&rtn.id));
-                cache
-                    .1
-                    .get_mut(&rtn.age)
+                    .choose(&mut rng)
                     .unwrap()
-                    .retain(|m| !m.id.eq(&rtn.id));
-                return (Some(rtn.id), cache);
+            } else {
+                neighbours.choose(&mut rng).unwrap()
+            };
+            let candidate = city.citizens.get(candidate_id).unwrap();
+            if found.contains(candidate_id) || !is_friend_candidate(mind, candidate, &city.culture)
+            {
+                continue;
             }
-        }
-        return (None, cache);
-    }
-
-    fn hash_population_by_age<'a>(population: &'a Population) -> AgeCache<'a> {
-        let mut output: AgeCache = HashMap::new();
-        for mind in population.values() {
-            if output.contains_key(&mind.age) {
-                let mut current = output.get(&mind.age).unwrap().clone();
-                current.push(mind);
-                output.insert(mind.age, current);
+            let multiplier = if candidate.gender.eq(&mind.gender) {
+                FRIEND_MULTIPLIER_SAME_GENDER
             } else {
-                output.insert(mind.age, vec![mind]);
+                FRIEND_MULTIPLER_DIFFERENT_GENDER
+            };
+            if rng.gen::<f32>() < multiplier * FRIEND_RATE * 2.0 {
+                found.push(candidate_id.clone());
             }
         }
-        return output;
+        return found;
     }
 
-    fn hash_population_by_inst_visits(population: &Population) {
-        let mut cache: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
-        for mind in population.values() {
-            let (shopping_locs, _) = get_habitual_institutions(mind, &crate::city::institutions::visits::visits::VisitType::Shopping);
-            let (social_locs, _) = get_habitual_institutions(mind, &crate::city::institutions::visits::visits::VisitType::Social);
-            let locs = vec![shopping_locs, social_locs].concat();
-            for loc in locs {
-                if !cache.contains_key(loc) {
-                    cache.insert(loc.clone(), vec![mind.id]);
-                } else {
-                    let current = cache.get_mut(loc).unwrap();
-                    current.push(mind.id.clone());
-                    drop(current);
-                }
+    fn replace_social_verb(
+        city: &mut City,
+        id_1: &Uuid,
+        id_2: &Uuid,
+        new_verb: Option<RelationVerb>,
+    ) {
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let mind = city.citizens.get_mut(id).unwrap();
+            mind.relations
+                .retain(|(v, i)| !(EVOLVING_RELATIONS.contains(v) && i.eq(other)));
+            if new_verb.is_some() {
+                mind.relations
+                    .push((new_verb.clone().unwrap(), other.clone()));
             }
         }
+        if new_verb.is_none() {
+            clear_affinity(city, id_1, id_2);
+        }
+        if !new_verb.eq(&Some(RelationVerb::Grudge)) {
+            clear_grudge_cause(city, id_1, id_2);
+        }
     }
 
     fn process_friend_evolution<'a>(mind_id: &Uuid, city: &'a mut City) -> &'a mut City {
-        let mut rng = rand::thread_rng();
         let mind_ref = city.citizens.get(mind_id).unwrap();
         for (verb, id) in mind_ref.relations.clone() {
-            let mind = city.citizens.get_mut(&mind_id).unwrap();
-            match verb {
-                RelationVerb::Acquaintance => {
-                    if rng.gen::<f32>() < ACQUAINTANCE_DECAY_CHANCE {
-                        mind.relations.retain(|(v, i)| !(v.eq(&verb) && i.eq(&id)));
-
-                        drop(mind);
-                        let relation = city.citizens.get_mut(&id).unwrap();
-                        relation
-                            .relations
-                            .retain(|(v, i)| !(v.eq(&verb) && i.eq(&mind_id)));
-                    } else if rng.gen::<f32>() < ACQUAINTANCE_UPGRADE_CHANCE {
-                        mind.relations.retain(|(v, i)| !(v.eq(&verb) && i.eq(&id)));
-                        mind.relations.push((RelationVerb::Friend, id.clone()));
-
-                        drop(mind);
-                        let relation = city.citizens.get_mut(&id).unwrap();
-                        relation
-                            .relations
-                            .retain(|(v, i)| !(v.eq(&verb) && i.eq(&mind_id)));
-                        relation
-                            .relations
-                            .push((RelationVerb::Friend, mind_id.clone()));
-                    }
-                }
-                RelationVerb::Friend => {
-                    if rng.gen::<f32>() < FRIEND_DECAY_CHANCE {
-                        mind.relations.retain(|(v, i)| !(v.eq(&verb) && i.eq(&id)));
-                        mind.relations
-                            .push((RelationVerb::Acquaintance, id.clone()));
-                        drop(mind);
-                        let relation = city.citizens.get_mut(&id).unwrap();
-                        relation
-                            .relations
-                            .retain(|(v, i)| !(v.eq(&verb) && i.eq(&mind_id)));
-                        relation
-                            .relations
-                            .push((RelationVerb::Acquaintance, mind_id.clone()));
-                    } else if rng.gen::<f32>() < FRIEND_UPGRADE_CHANCE {
-                        mind.relations.retain(|(v, i)| !(v.eq(&verb) && i.eq(&id)));
-                        mind.relations.push((RelationVerb::CloseFriend, id.clone()));
-
-                        drop(mind);
-                        let relation = city.citizens.get_mut(&id).unwrap();
-                        relation
-                            .relations
-                            .retain(|(v, i)| !(v.eq(&verb) && i.eq(&mind_id)));
-                        relation
-                            .relations
-                            .push((RelationVerb::CloseFriend, mind_id.clone()));
-                    }
-                }
-                RelationVerb::CloseFriend => {
-                    if rng.gen::<f32>() < CLOSE_FRIEND_DECAY_CHANCE {
-                        mind.relations.retain(|(v, i)| !(v.eq(&verb) && i.eq(&id)));
-                        mind.relations.push((RelationVerb::Friend, id.clone()));
-                        drop(mind);
-                        let relation = city.citizens.get_mut(&id).unwrap();
-                        relation
-                            .relations
-                            .retain(|(v, i)| !(v.eq(&verb) && i.eq(&mind_id)));
-                        relation
-                            .relations
-                            .push((RelationVerb::Friend, mind_id.clone()));
-                    }
+            // each tie is scored once a year, from the side with the lower id
+            if !EVOLVING_RELATIONS.contains(&verb) || id < *mind_id {
+                continue;
+            }
+            let other = city.citizens.get(&id);
+            if other.is_none() || !other.unwrap().alive {
+                continue;
+            }
+            let score = update_affinity(city, &verb, mind_id, &id);
+            let new_verb = social_verb_for_affinity(score);
+            if !new_verb.eq(&Some(verb.clone())) {
+                replace_social_verb(city, mind_id, &id, new_verb.clone());
+                if new_verb.eq(&Some(RelationVerb::Grudge)) {
+                    let neighbours = are_neighbours(city.citizens.get(mind_id).unwrap(), &id);
+                    let cause = if neighbours {
+                        GrudgeCause::NeighbourDispute
+                    } else if verb.eq(&RelationVerb::Acquaintance) {
+                        GrudgeCause::Quarrel
+                    } else {
+                        GrudgeCause::FailedFriendship
+                    };
+                    set_grudge_cause(city, mind_id, &id, &cause);
+                    let mind = city.citizens.get(mind_id).unwrap().clone();
+                    let other = city.citizens.get(&id).unwrap().clone();
+                    add_grudge_to_mind_log(
+                        city.citizens.get_mut(mind_id).unwrap(),
+                        city.year,
+                        &other,
+                        describe_grudge_cause(&cause),
+                    );
+                    add_grudge_to_mind_log(
+                        city.citizens.get_mut(&id).unwrap(),
+                        city.year,
+                        &mind,
+                        describe_grudge_cause(&cause),
+                    );
                 }
-                _ => {}
             }
         }
         return city;
     }
 
     pub fn link_friends_within_population_by_year<'a>(city: &'a mut City) -> &'a mut City {
-        let mut rng = rand::thread_rng();
-        let mut friendable_population = city.citizens.clone();
-        friendable_population.retain(|_id, m| m.alive);
-        let ids = friendable_population.keys();
-
-        let population_by_age = hash_population_by_age(&friendable_population);
-
-        for mind_id in ids {
-            // city.citizens.shuffle(&mut rng);
-            let mind = city.citizens.get(&mind_id).unwrap().clone();
-            let mut friend_cache: (AgeCache, AgeCache) = (HashMap::new(), HashMap::new());
-            let mut cache = &mut friend_cache;
-
-            let mut pending_friends: Vec<Uuid> = vec![];
+        let mut rng = thread_rng();
+        let (members, contexts) = hash_population_by_context(city);
+        let mut ids: Vec<Uuid> = contexts.keys().cloned().collect();
+        ids.shuffle(&mut rng);
 
+        for mind_id in ids.iter() {
+            let mind = city.citizens.get(mind_id).unwrap();
             let friend_count = mind
                 .relations
                 .iter()
                 .filter(|(v, _id)| SOCIAL_RELATIONS.contains(&v))
                 .count();
 
-            let acquaintances_to_add_count =
-                (((rng.gen::<f32>() * FRIEND_OUTGOING_MAX) - (friend_count as f32)) as u32).max(0);
-
-            for _i in 0..acquaintances_to_add_count {
-                // Extremely slow line
-                let possible_friend_id: Option<Uuid>;
+            let acquaintances_to_add_count = ((rng.gen::<f32>() * FRIEND_OUTGOING_MAX)
+                - (friend_count as f32))
+                .max(0.0) as usize;
 
-                (possible_friend_id, cache) = get_friend(
-                    &mind,
-                    &population_by_age,
-                    cache,
-                    &city.culture,
-                    &pending_friends,
-                );
-
-                if possible_friend_id.is_some() {
-                    let friend_id = possible_friend_id.unwrap();
-                    pending_friends.push(friend_id.clone());
-
-                    let friend_m = city.citizens.get_mut(&friend_id).unwrap();
-                    friend_m
-                        .relations
-                        .push((RelationVerb::Acquaintance, mind.id.clone()));
-                    // let cache_entry_0 = cache.0.get(&friend_m.age).unwrap();
-                    // if cache_entry_0.iter().any(|m| m.id.eq(&friend_m.id)) {
-                    //     println!("Repeat Cache Persisting - 0")
-                    // }
-                    // let cache_entry_1 = cache.1.get(&friend_m.age).unwrap();
-                    // if cache_entry_1.iter().any(|m| m.id.eq(&friend_m.id)) {
-                    //     println!("Repeat Cache Persisting - 1")
-                    // }
-                    drop(friend_m);
-
-                    let mind_m = city.citizens.get_mut(&mind.id).unwrap();
-                    mind_m
-                        .relations
-                        .push((RelationVerb::Acquaintance, friend_id.clone()));
-                    drop(mind_m);
-                }
+            let new_acquaintances = find_new_acquaintances(
+                mind,
+                acquaintances_to_add_count,
+                city,
+                &members,
+                contexts.get(mind_id).unwrap(),
+            );
+            for friend_id in new_acquaintances {
+                city.citizens
+                    .get_mut(&friend_id)
+                    .unwrap()
+                    .relations
+                    .push((RelationVerb::Acquaintance, mind_id.clone()));
+                city.citizens
+                    .get_mut(mind_id)
+                    .unwrap()
+                    .relations
+                    .push((RelationVerb::Acquaintance, friend_id.clone()));
             }
-            
+
             process_friend_evolution(mind_id, city);
         }
         return city;
@@ -0,0 +1,351 @@
+pub mod grudges {
+    use rand::Rng;
+    use strum_macros::Display;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::mind::mind::{add_grudge_to_mind_log, Mind};
+    use crate::city::population::mind::naming::naming::get_full_name;
+    use crate::city::population::mind::relations::affinity::affinity::{
+        get_affinity, set_affinity,
+    };
+    use crate::city::population::mind::relations::friends::friends::SOCIAL_RELATIONS;
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng, HashMap};
+
+    // Below this affinity a grudge may drag both families into a feud
+    const FEUD_THRESHOLD: i32 = -75;
+    const FEUD_ESCALATION_CHANCE: f32 = 0.05;
+    const FAMILY_VERBS: [RelationVerb; 4] = [
+        RelationVerb::Parent,
+        RelationVerb::Child,
+        RelationVerb::Sibling,
+        RelationVerb::Spouse,
+    ];
+    const FEUD_HOOK_LIMIT: usize = 10;
+    const GRUDGE_HOOK_LIMIT: usize = 10;
+
+    #[derive(PartialEq, Debug, Clone, Display)]
+    pub enum GrudgeCause {
+        Breakup,
+        Divorce,
+        Sacking,
+        BusinessRivalry,
+        FailedFriendship,
+        Quarrel,
+        Crime,
+        Feud,
+        NeighbourDispute,
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Feud {
+        pub id: Uuid,
+        pub instigators: (Uuid, Uuid),
+        pub families: (String, String),
+        pub cause: GrudgeCause,
+        pub members: Vec<Uuid>,
+        pub year: usize,
+        pub ended: Option<usize>,
+    }
+
+    // How badly each kind of falling out sours a relationship to begin with
+    fn grudge_affinity(cause: &GrudgeCause) -> i32 {
+        return match cause {
+            GrudgeCause::Crime => -80,
+            GrudgeCause::Divorce => -70,
+            GrudgeCause::Feud => -60,
+            GrudgeCause::Breakup => -55,
+            GrudgeCause::Sacking => -50,
+            GrudgeCause::BusinessRivalry => -45,
+            GrudgeCause::FailedFriendship
+            | GrudgeCause::Quarrel
+            | GrudgeCause::NeighbourDispute => -40,
+        };
+    }
+
+    pub fn describe_grudge_cause(cause: &GrudgeCause) -> &str {
+        return match cause {
+            GrudgeCause::Breakup => "a bitter breakup",
+            GrudgeCause::Divorce => "a divorce",
+            GrudgeCause::Sacking => "a sacking",
+            GrudgeCause::BusinessRivalry => "a business rivalry",
+            GrudgeCause::FailedFriendship => "a friendship gone sour",
+            GrudgeCause::Quarrel => "a quarrel",
+            GrudgeCause::Crime => "a crime",
+            GrudgeCause::Feud => "a family feud",
+            GrudgeCause::NeighbourDispute => "a dispute between neighbours",
+        };
+    }
+
+    pub fn has_grudge(mind: &Mind, other_id: &Uuid) -> bool {
+        return mind
+            .relations
+            .contains(&(RelationVerb::Grudge, other_id.clone()));
+    }
+
+    // Record the cause on both sides of a grudge that already exists as a relation
+    pub fn set_grudge_cause(city: &mut City, id_1: &Uuid, id_2: &Uuid, cause: &GrudgeCause) {
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let mind = city.citizens.get_mut(id).unwrap();
+            mind.grudge_causes.insert(other.clone(), cause.clone());
+        }
+    }
+
+    pub fn clear_grudge_cause(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let mind = city.citizens.get_mut(id).unwrap();
+            mind.grudge_causes.remove(other);
+        }
+    }
+
+    // Turn whatever social tie two people share into a grudge, keeping the original cause if one is held already
+    pub fn add_grudge(city: &mut City, id_1: &Uuid, id_2: &Uuid, cause: GrudgeCause) {
+        if id_1.eq(id_2) {
+            return;
+        }
+        let m1 = city.citizens.get(id_1);
+        let m2 = city.citizens.get(id_2);
+        if m1.is_none() || m2.is_none() || !m1.unwrap().alive || !m2.unwrap().alive {
+            return;
+        }
+        let existing = has_grudge(m1.unwrap(), id_2);
+        let score =
+            get_affinity(m1.unwrap(), &RelationVerb::Grudge, id_2).min(grudge_affinity(&cause));
+        set_affinity(city, id_1, id_2, score);
+        if existing {
+            return;
+        }
+        let year = city.year;
+        for (id, other) in [(id_1, id_2), (id_2, id_1)] {
+            let other_mind = city.citizens.get(other).unwrap().clone();
+            let mind = city.citizens.get_mut(id).unwrap();
+            mind.relations.retain(|(v, i)| {
+                !((SOCIAL_RELATIONS.contains(v) || v.eq(&RelationVerb::Grudge)) && i.eq(other))
+            });
+            mind.relations.push((RelationVerb::Grudge, other.clone()));
+            mind.grudge_causes.insert(other.clone(), cause.clone());
+            add_grudge_to_mind_log(mind, year, &other_mind, describe_grudge_cause(&cause));
+        }
+    }
+
+    fn close_family(mind: &Mind, city: &City) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, id)| {
+                FAMILY_VERBS.contains(v)
+                    && city
+                        .citizens
+                        .get(id)
+                        .is_some_and(|m| m.alive && m.age >= city.culture.adult_age)
+            })
+            .map(|(_v, id)| id.clone())
+            .collect();
+    }
+
+    fn in_active_feud(city: &City, id: &Uuid) -> bool {
+        return city
+            .feuds
+            .values()
+            .any(|f| f.ended.is_none() && f.members.contains(id));
+    }
+
+    fn start_feud(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        let m1 = city.citizens.get(id_1).unwrap().clone();
+        let m2 = city.citizens.get(id_2).unwrap().clone();
+        let family_1 = close_family(&m1, city);
+        let family_2 = close_family(&m2, city);
+        // relatives of both sides take up the quarrel against the other side's instigator
+        for relative in family_1.iter() {
+            if !relative.eq(id_2) {
+                add_grudge(city, relative, id_2, GrudgeCause::Feud);
+            }
+        }
+        for relative in family_2.iter() {
+            if !relative.eq(id_1) {
+                add_grudge(city, relative, id_1, GrudgeCause::Feud);
+            }
+        }
+        let cause = m1
+            .grudge_causes
+            .get(id_2)
+            .cloned()
+            .unwrap_or(GrudgeCause::Quarrel);
+        let feud = Feud {
+            id: random_uuid(),
+            instigators: (id_1.clone(), id_2.clone()),
+            families: (m1.last_name.clone(), m2.last_name.clone()),
+            cause,
+            members: vec![vec![id_1.clone(), id_2.clone()], family_1, family_2].concat(),
+            year: city.year,
+            ended: None,
+        };
+        city.feuds.insert(feud.id.clone(), feud);
+    }
+
+    // Feuds end once the instigators have made peace, or neither is left to carry it on
+    fn end_feuds(city: &mut City) {
+        let year = city.year;
+        let mut ended: Vec<Uuid> = Vec::new();
+        for feud in city.feuds.values().filter(|f| f.ended.is_none()) {
+            let (id_1, id_2) = &feud.instigators;
+            let m1 = city.citizens.get(id_1).unwrap();
+            let m2 = city.citizens.get(id_2).unwrap();
+            if !(m1.alive && m2.alive) || !has_grudge(m1, id_2) {
+                ended.push(feud.id.clone());
+            }
+        }
+        for id in ended {
+            city.feuds.get_mut(&id).unwrap().ended = Some(year);
+        }
+    }
+
+    pub fn escalate_feuds_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        end_feuds(city);
+        let mut bitter: Vec<(Uuid, Uuid)> = Vec::new();
+        for mind in city.citizens.values().filter(|m| m.alive) {
+            for (verb, id) in mind.relations.iter() {
+                // grudges born of a feud don't start new ones
+                if verb.eq(&RelationVerb::Grudge)
+                    && mind.id < *id
+                    && !mind.grudge_causes.get(id).eq(&Some(&GrudgeCause::Feud))
+                    && get_affinity(mind, verb, id) <= FEUD_THRESHOLD
+                {
+                    bitter.push((mind.id.clone(), id.clone()));
+                }
+            }
+        }
+        for (id_1, id_2) in bitter {
+            if !city.citizens.get(&id_2).unwrap().alive
+                || in_active_feud(city, &id_1)
+                || in_active_feud(city, &id_2)
+                || rng.gen::<f32>() > FEUD_ESCALATION_CHANCE
+            {
+                continue;
+            }
+            start_feud(city, &id_1, &id_2);
+        }
+        return city;
+    }
+
+    // Blame for a sacking lands on someone who kept their job
+    pub fn blame_for_sacking(city: &mut City, sacked_id: &Uuid, employer_id: &Uuid) {
+        let remaining: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.employer.eq(&Some(employer_id.clone())))
+            .map(|m| m.id)
+            .collect();
+        if remaining.len() > 0 {
+            add_grudge(
+                city,
+                sacked_id,
+                &random_pick(&remaining),
+                GrudgeCause::Sacking,
+            );
+        }
+    }
+
+    // A new business picks a fight with someone already working in its trade
+    pub fn start_business_rivalry(city: &mut City, founder_id: &Uuid, institution_id: &Uuid) {
+        let institution = city.institutions.get(institution_id).unwrap();
+        let competitors: Vec<&Uuid> = city
+            .institutions
+            .values()
+            .filter(|i| {
+                !i.id.eq(institution_id) && i.institute_type.eq(&institution.institute_type)
+            })
+            .map(|i| &i.id)
+            .collect();
+        let rivals: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| {
+                m.alive && m.employer.is_some() && competitors.contains(&&m.employer.unwrap())
+            })
+            .map(|m| m.id)
+            .collect();
+        if rivals.len() > 0 {
+            add_grudge(
+                city,
+                founder_id,
+                &random_pick(&rivals),
+                GrudgeCause::BusinessRivalry,
+            );
+        }
+    }
+
+    fn name_of(city: &City, id: &Uuid) -> String {
+        return get_full_name(city.citizens.get(id).unwrap());
+    }
+
+    // Story prompts drawn from active feuds and the bitterest grudges in the city
+    pub fn adventure_hooks(city: &City) -> Vec<String> {
+        let mut output: Vec<String> = Vec::new();
+        let mut feuding: Vec<(Uuid, Uuid)> = Vec::new();
+        let mut feuds: Vec<(&Feud, usize)> = city
+            .feuds
+            .values()
+            .filter(|f| f.ended.is_none())
+            .map(|f| {
+                let members = f
+                    .members
+                    .iter()
+                    .filter(|id| city.citizens.get(id).unwrap().alive)
+                    .count();
+                (f, members)
+            })
+            .collect();
+        feuds.sort_by_key(|(f, members)| (usize::MAX - members, f.year));
+        for (feud, members) in feuds.iter().take(FEUD_HOOK_LIMIT) {
+            let (id_1, id_2) = &feud.instigators;
+            feuding.push((id_1.clone(), id_2.clone()));
+            let families = if feud.families.0.eq(&feud.families.1) {
+                format!("A feud has divided the {} family", feud.families.0)
+            } else {
+                format!(
+                    "The {} and {} families have feuded",
+                    feud.families.0, feud.families.1
+                )
+            };
+            output.push(format!(
+                "{} since year {}, when {} and {} fell out over {}. {} people are caught up in it.",
+                families,
+                feud.year,
+                name_of(city, id_1),
+                name_of(city, id_2),
+                describe_grudge_cause(&feud.cause),
+                members
+            ));
+        }
+        let mut grudges: HashMap<(Uuid, Uuid), (i32, GrudgeCause)> = HashMap::default();
+        for mind in city.citizens.values().filter(|m| m.alive) {
+            for (id, cause) in mind.grudge_causes.iter() {
+                if mind.id < *id
+                    && !cause.eq(&GrudgeCause::Feud)
+                    && !feuding.contains(&(mind.id, id.clone()))
+                    && !feuding.contains(&(id.clone(), mind.id))
+                    && city.citizens.get(id).unwrap().alive
+                {
+                    grudges.insert(
+                        (mind.id.clone(), id.clone()),
+                        (get_affinity(mind, &RelationVerb::Grudge, id), cause.clone()),
+                    );
+                }
+            }
+        }
+        let mut grudges: Vec<((Uuid, Uuid), (i32, GrudgeCause))> = grudges.into_iter().collect();
+        grudges.sort_by_key(|(_ids, (score, _cause))| *score);
+        for ((id_1, id_2), (_score, cause)) in grudges.iter().take(GRUDGE_HOOK_LIMIT) {
+            output.push(format!(
+                "{} and {} have not forgiven each other for {}.",
+                name_of(city, id_1),
+                name_of(city, id_2),
+                describe_grudge_cause(cause)
+            ));
+        }
+        return output;
+    }
+}
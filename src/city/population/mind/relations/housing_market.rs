@@ -0,0 +1,370 @@
+pub mod housing_market {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::building::building::{FloorAreaType, Landlord};
+    use crate::city::city::City;
+    use crate::city::population::household::household::set_dwelling;
+    use crate::city::population::mind::appearance::appearance::is_wealthy_employer;
+    use crate::city::population::mind::mind::add_event_to_mind_log;
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::city::population::mind::relations::residences::residences::{
+        find_dwellings, Dwelling,
+    };
+    use crate::utils::utils::{thread_rng, HashMap, HashSet};
+
+    const RENT_PER_ROOM: f32 = 1.0;
+    // Each storey above the ground adds to the rent
+    const FLOOR_PREMIUM: f32 = 0.05;
+    // Rents sink to half their base in an empty neighbourhood and reach one and a half times it when full
+    const MIN_DEMAND_MULTIPLIER: f32 = 0.5;
+    const PRICE_TO_RENT_RATIO: i32 = 15;
+    // Years of unpaid rent a landlord puts up with before evicting
+    const ARREARS_LIMIT: i32 = 2;
+    // Annual chance a tenant who can afford their home buys it
+    const HOME_PURCHASE_CHANCE: f32 = 0.5;
+    // Annual chance a well-off household buys a home to let out
+    const INVESTMENT_CHANCE: f32 = 0.1;
+    // Investors only buy when they can cover this many times the price
+    const INVESTOR_MARGIN: i32 = 2;
+    const INSTITUTION_INVESTMENT_CHANCE: f32 = 0.02;
+
+    pub fn dwelling_price(dwelling: &Dwelling) -> i32 {
+        return dwelling.rent * PRICE_TO_RENT_RATIO;
+    }
+
+    fn is_owner_occupied(dwelling: &Dwelling, members: &Vec<Uuid>) -> bool {
+        return match &dwelling.landlord {
+            Some(Landlord::Citizen(owner)) => members.contains(owner),
+            _ => false,
+        };
+    }
+
+    fn set_landlord(city: &mut City, area_id: &Uuid, landlord: Option<Landlord>) {
+        for building in city.buildings.values_mut() {
+            for floor in building.floors.iter_mut() {
+                for area in floor.areas.iter_mut() {
+                    if area.id.eq(area_id) {
+                        area.landlord = landlord;
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    // Rents follow the size of the home, its storey, the standing of the neighbourhood and how full it is
+    fn update_rents(city: &mut City) {
+        let occupied: HashSet<Uuid> = city
+            .households
+            .values()
+            .filter_map(|h| h.dwelling)
+            .collect();
+        let mut demand: HashMap<Uuid, (usize, usize)> = HashMap::default();
+        for dwelling in find_dwellings(city).values() {
+            let entry = demand.entry(dwelling.location_id).or_insert((0, 0));
+            entry.0 += 1;
+            if occupied.contains(&dwelling.area_id) {
+                entry.1 += 1;
+            }
+        }
+        let desirability: HashMap<Uuid, u32> = city
+            .areas
+            .values()
+            .map(|l| (l.id.clone(), l.desirability))
+            .collect();
+        for building in city.buildings.values_mut() {
+            let location_id = building.location_id.unwrap();
+            let (total, taken) = demand.get(&location_id).cloned().unwrap_or((1, 0));
+            let demand_multiplier = MIN_DEMAND_MULTIPLIER + taken as f32 / total.max(1) as f32;
+            let location_desirability = *desirability.get(&location_id).unwrap() as f32;
+            for floor in building.floors.iter_mut() {
+                let floor_premium = 1.0 + floor.level.max(0) as f32 * FLOOR_PREMIUM;
+                for area in floor.areas.iter_mut().filter(|a| {
+                    a.area_type.eq(&FloorAreaType::Apartment) && a.owning_institution.is_none()
+                }) {
+                    let base = area.rooms as f32 * location_desirability * RENT_PER_ROOM;
+                    area.rent = ((base * floor_premium * demand_multiplier).round() as i32).max(1);
+                }
+            }
+        }
+    }
+
+    // Property left by the dead passes to a widowed spouse, then a child, or else goes back on the market
+    fn pass_on_property(city: &mut City) {
+        let estates: Vec<(Dwelling, Uuid)> = find_dwellings(city)
+            .into_values()
+            .filter_map(|d| match d.landlord {
+                Some(Landlord::Citizen(owner)) => Some((d, owner)),
+                _ => None,
+            })
+            .filter(|(_d, owner)| city.citizens.get(owner).is_some_and(|o| !o.alive))
+            .collect();
+        for (dwelling, owner_id) in estates {
+            let owner = city.citizens.get(&owner_id).unwrap();
+            let heir = [
+                RelationVerb::Spouse,
+                RelationVerb::Child,
+                RelationVerb::AdoptedChild,
+            ]
+            .iter()
+            .find_map(|verb| {
+                owner
+                    .relations
+                    .iter()
+                    .filter(|(v, _id)| v.eq(verb))
+                    .map(|(_v, id)| city.citizens.get(id).unwrap())
+                    .find(|m| m.alive)
+                    .map(|m| m.id.clone())
+            });
+            set_landlord(
+                city,
+                &dwelling.area_id,
+                heir.map(|id| Landlord::Citizen(id)),
+            );
+            if heir.is_some() {
+                let year = city.year;
+                let heir = city.citizens.get_mut(&heir.unwrap()).unwrap();
+                add_event_to_mind_log(
+                    heir,
+                    year,
+                    &format!("inherited {} {}", dwelling.name, dwelling.building_name),
+                );
+            }
+        }
+    }
+
+    // Tenants pay what they can towards their rent and any arrears, and are evicted once too far behind
+    fn collect_rent(city: &mut City) {
+        let dwellings = find_dwellings(city);
+        let tenancies: Vec<(Uuid, Dwelling)> = city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_some_and(|d| dwellings.contains_key(&d)))
+            .map(|h| {
+                (
+                    h.id.clone(),
+                    dwellings.get(&h.dwelling.unwrap()).unwrap().clone(),
+                )
+            })
+            .filter(|(id, d)| !is_owner_occupied(d, &city.households.get(id).unwrap().members))
+            .collect();
+        for (household_id, dwelling) in tenancies {
+            let household = city.households.get_mut(&household_id).unwrap();
+            let due = dwelling.rent + household.rent_arrears;
+            let paid = due.min(household.budget);
+            household.budget -= paid;
+            household.rent_arrears = due - paid;
+            let evicted = household.rent_arrears > dwelling.rent * ARREARS_LIMIT;
+            match &dwelling.landlord {
+                Some(Landlord::Citizen(owner)) => {
+                    let landlord_household = city.citizens.get(owner).unwrap().household;
+                    if landlord_household.is_some() {
+                        let landlord_household = city
+                            .households
+                            .get_mut(&landlord_household.unwrap())
+                            .unwrap();
+                        landlord_household.budget += paid;
+                    }
+                }
+                Some(Landlord::Institution(id)) => {
+                    let institution = city.institutions.get_mut(id);
+                    if institution.is_some() {
+                        institution.unwrap().wealth += paid as usize;
+                    }
+                }
+                None => {}
+            }
+            if evicted {
+                let household = city.households.get_mut(&household_id).unwrap();
+                household.rent_arrears = 0;
+                let members = household.members.clone();
+                set_dwelling(city, &household_id, None);
+                let year = city.year;
+                for id in members {
+                    let mind = city.citizens.get_mut(&id).unwrap();
+                    add_event_to_mind_log(
+                        mind,
+                        year,
+                        &format!(
+                            "was evicted from {} {} for unpaid rent",
+                            dwelling.name, dwelling.building_name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // Tenants buy the homes they rent from no one in particular, and the well-off buy homes to let
+    fn trade_property(city: &mut City) {
+        let mut rng = thread_rng();
+        let dwellings = find_dwellings(city);
+        let mut for_sale: Vec<Dwelling> = dwellings
+            .values()
+            .filter(|d| d.landlord.is_none())
+            .cloned()
+            .collect();
+        for_sale.shuffle(&mut rng);
+        let mut sales: Vec<(Uuid, Dwelling, bool)> = Vec::new();
+        for household in city.households.values() {
+            let home = household.dwelling.map(|d| dwellings.get(&d)).flatten();
+            // a home already bought by an investor this year is no longer for sale
+            if home.is_some_and(|h| h.landlord.is_none())
+                && household.budget >= dwelling_price(home.unwrap())
+                && !sales
+                    .iter()
+                    .any(|(_h, s, _o)| s.area_id.eq(&home.unwrap().area_id))
+                && rng.gen::<f32>() < HOME_PURCHASE_CHANCE
+            {
+                sales.push((household.id.clone(), home.unwrap().clone(), true));
+                continue;
+            }
+            if home.is_none() || rng.gen::<f32>() >= INVESTMENT_CHANCE {
+                continue;
+            }
+            let investment = for_sale.iter().find(|d| {
+                !d.area_id.eq(&home.unwrap().area_id)
+                    && household.budget >= dwelling_price(d) * INVESTOR_MARGIN
+                    && !sales.iter().any(|(_h, s, _o)| s.area_id.eq(&d.area_id))
+            });
+            if investment.is_some() {
+                sales.push((household.id.clone(), investment.unwrap().clone(), false));
+            }
+        }
+        let year = city.year;
+        for (household_id, dwelling, own_home) in sales {
+            let household = city.households.get_mut(&household_id).unwrap();
+            household.budget -= dwelling_price(&dwelling);
+            let head = household.head.clone();
+            set_landlord(
+                city,
+                &dwelling.area_id,
+                Some(Landlord::Citizen(head.clone())),
+            );
+            for_sale.retain(|d| !d.area_id.eq(&dwelling.area_id));
+            let event = if own_home {
+                format!(
+                    "bought their home at {} {}",
+                    dwelling.name, dwelling.building_name
+                )
+            } else {
+                format!(
+                    "bought {} {} to let out",
+                    dwelling.name, dwelling.building_name
+                )
+            };
+            add_event_to_mind_log(city.citizens.get_mut(&head).unwrap(), year, &event);
+        }
+        // wealthy businesses put spare capital into property
+        let investors: Vec<Uuid> = city
+            .institutions
+            .values()
+            .filter(|i| !i.public && is_wealthy_employer(i))
+            .filter(|_i| rng.gen::<f32>() < INSTITUTION_INVESTMENT_CHANCE)
+            .map(|i| i.id.clone())
+            .collect();
+        for institution_id in investors {
+            let wealth = city.institutions.get(&institution_id).unwrap().wealth;
+            let affordable = for_sale
+                .iter()
+                .position(|d| wealth >= dwelling_price(d) as usize);
+            if affordable.is_none() {
+                continue;
+            }
+            let dwelling = for_sale.remove(affordable.unwrap());
+            let institution = city.institutions.get_mut(&institution_id).unwrap();
+            institution.wealth -= dwelling_price(&dwelling) as usize;
+            set_landlord(
+                city,
+                &dwelling.area_id,
+                Some(Landlord::Institution(institution_id)),
+            );
+        }
+    }
+
+    pub fn run_housing_market_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        update_rents(city);
+        pass_on_property(city);
+        collect_rent(city);
+        trade_property(city);
+        return city;
+    }
+
+    // Heads of renting households and the citizens they rent from know each other as tenant and landlord
+    pub fn link_tenancies<'a>(city: &'a mut City) -> &'a mut City {
+        for mind in city.citizens.values_mut() {
+            mind.relations
+                .retain(|(v, _id)| !v.eq(&RelationVerb::Landlord) && !v.eq(&RelationVerb::Tenant));
+        }
+        let dwellings = find_dwellings(city);
+        let tenancies: Vec<(Uuid, Uuid)> = city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_some_and(|d| dwellings.contains_key(&d)))
+            .filter_map(
+                |h| match &dwellings.get(&h.dwelling.unwrap()).unwrap().landlord {
+                    Some(Landlord::Citizen(owner)) if !h.members.contains(owner) => {
+                        Some((h.head.clone(), owner.clone()))
+                    }
+                    _ => None,
+                },
+            )
+            .filter(|(_head, owner)| city.citizens.get(owner).is_some_and(|o| o.alive))
+            .collect();
+        for (head, owner) in tenancies {
+            city.citizens
+                .get_mut(&head)
+                .unwrap()
+                .relations
+                .push((RelationVerb::Landlord, owner.clone()));
+            city.citizens
+                .get_mut(&owner)
+                .unwrap()
+                .relations
+                .push((RelationVerb::Tenant, head));
+        }
+        return city;
+    }
+
+    pub fn print_housing_statistics(city: &City) -> String {
+        let mut output = String::new();
+        let dwellings = find_dwellings(city);
+        let mut owner_occupied = 0;
+        let mut privately_let = 0;
+        let mut institution_let = 0;
+        let mut unowned = 0;
+        for household in city.households.values() {
+            let home = household.dwelling.map(|d| dwellings.get(&d)).flatten();
+            if home.is_none() {
+                continue;
+            }
+            let home = home.unwrap();
+            match &home.landlord {
+                Some(Landlord::Citizen(owner)) if household.members.contains(owner) => {
+                    owner_occupied += 1
+                }
+                Some(Landlord::Citizen(_owner)) => privately_let += 1,
+                Some(Landlord::Institution(_id)) => institution_let += 1,
+                None => unowned += 1,
+            }
+        }
+        let rents: Vec<i32> = dwellings.values().map(|d| d.rent).collect();
+        let in_arrears = city
+            .households
+            .values()
+            .filter(|h| h.rent_arrears > 0)
+            .count();
+        output.push_str(&format!("Owner Occupied Homes: {}\n", owner_occupied));
+        output.push_str(&format!("Privately Let Homes: {}\n", privately_let));
+        output.push_str(&format!("Homes Let By Institutions: {}\n", institution_let));
+        output.push_str(&format!("Homes Let Without A Landlord: {}\n", unowned));
+        output.push_str(&format!(
+            "Average Rent: {}\n",
+            rents.iter().sum::<i32>() / rents.len().max(1) as i32
+        ));
+        output.push_str(&format!("Households In Arrears: {}\n", in_arrears));
+        return output;
+    }
+}
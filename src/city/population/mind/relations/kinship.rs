@@ -0,0 +1,333 @@
+pub mod kinship {
+    use uuid::Uuid;
+
+    use crate::city::population::mind::mind::{Gender, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::city::population::population::Population;
+    use crate::culture::culture::CultureConfig;
+    use crate::utils::utils::HashMap;
+
+    // How many generations up the family tree to look for a shared ancestor
+    const MAX_KIN_DEPTH: u32 = 6;
+
+    // What the first citizen is to the second
+    #[derive(PartialEq, Debug, Clone)]
+    pub enum Kinship {
+        Themselves,
+        // 1 for a parent, 2 for a grandparent and so on
+        Ancestor(u32),
+        Descendant(u32),
+        Sibling { half: bool },
+        // 1 for an aunt or uncle, 2 for a great-aunt or great-uncle and so on
+        Pibling(u32),
+        Nibling(u32),
+        Cousin { degree: u32, removal: u32 },
+        Spouse,
+        InLaw(Box<Kinship>),
+    }
+
+    fn parents(mind: &Mind) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+            .map(|(_v, id)| id.clone())
+            .collect();
+    }
+
+    fn spouses(mind: &Mind) -> Vec<Uuid> {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Spouse))
+            .map(|(_v, id)| id.clone())
+            .collect();
+    }
+
+    // Every known ancestor, including the citizen themselves, with the fewest generations to reach them
+    fn ancestors(mind: &Mind, population: &Population) -> HashMap<Uuid, u32> {
+        let mut found: HashMap<Uuid, u32> = HashMap::default();
+        found.insert(mind.id.clone(), 0);
+        let mut generation: Vec<&Mind> = vec![mind];
+        for depth in 1..=MAX_KIN_DEPTH {
+            let mut next: Vec<&Mind> = Vec::new();
+            for member in generation {
+                for parent_id in parents(member) {
+                    let parent = population.get(&parent_id);
+                    if parent.is_some() && !found.contains_key(&parent_id) {
+                        found.insert(parent_id, depth);
+                        next.push(parent.unwrap());
+                    }
+                }
+            }
+            if next.len() < 1 {
+                break;
+            }
+            generation = next;
+        }
+        return found;
+    }
+
+    fn blood_kinship(m1: &Mind, m2: &Mind, population: &Population) -> Option<Kinship> {
+        let ancestors_1 = ancestors(m1, population);
+        let ancestors_2 = ancestors(m2, population);
+        let closest = ancestors_1
+            .iter()
+            .filter_map(|(id, d1)| ancestors_2.get(id).map(|d2| (*d1, *d2)))
+            .min_by_key(|(d1, d2)| d1 + d2);
+        if closest.is_none() {
+            return None;
+        }
+        let (d1, d2) = closest.unwrap();
+        return Some(match (d1, d2) {
+            (0, 0) => Kinship::Themselves,
+            (0, d) => Kinship::Ancestor(d),
+            (d, 0) => Kinship::Descendant(d),
+            (1, 1) => {
+                let parents_1 = parents(m1);
+                let parents_2 = parents(m2);
+                let shared = parents_1.iter().filter(|p| parents_2.contains(p)).count();
+                Kinship::Sibling {
+                    half: shared < 2 && (parents_1.len() > 1 || parents_2.len() > 1),
+                }
+            }
+            (1, d) => Kinship::Pibling(d - 1),
+            (d, 1) => Kinship::Nibling(d - 1),
+            (d1, d2) => Kinship::Cousin {
+                degree: d1.min(d2) - 1,
+                removal: d1.abs_diff(d2),
+            },
+        });
+    }
+
+    // Walks parent and spouse links to work out what the first citizen is to the second, if anything
+    pub fn kinship(m1: &Mind, m2: &Mind, population: &Population) -> Option<Kinship> {
+        let blood = blood_kinship(m1, m2, population);
+        if blood.is_some() {
+            return blood;
+        }
+        if spouses(m1).contains(&m2.id) {
+            return Some(Kinship::Spouse);
+        }
+        // kin of the second citizen's spouse, or the spouse of the second citizen's kin
+        for spouse_id in spouses(m2) {
+            let spouse = population.get(&spouse_id);
+            if spouse.is_some() {
+                let term = blood_kinship(m1, spouse.unwrap(), population);
+                if term.is_some() {
+                    return Some(Kinship::InLaw(Box::new(term.unwrap())));
+                }
+            }
+        }
+        for spouse_id in spouses(m1) {
+            let spouse = population.get(&spouse_id);
+            if spouse.is_some() {
+                let term = blood_kinship(spouse.unwrap(), m2, population);
+                if term.is_some() {
+                    return Some(Kinship::InLaw(Box::new(term.unwrap())));
+                }
+            }
+        }
+        return None;
+    }
+
+    // Steps through the family tree between blood relatives, so siblings are 2 and first cousins 4
+    pub fn kinship_distance(kinship: &Kinship) -> Option<u32> {
+        return match kinship {
+            Kinship::Themselves => Some(0),
+            Kinship::Ancestor(d) | Kinship::Descendant(d) => Some(*d),
+            Kinship::Sibling { .. } => Some(2),
+            Kinship::Pibling(d) | Kinship::Nibling(d) => Some(d + 2),
+            Kinship::Cousin { degree, removal } => Some((degree + 1) * 2 + removal),
+            Kinship::Spouse | Kinship::InLaw(_) => None,
+        };
+    }
+
+    // Whether the culture forbids the two citizens from becoming a couple or raising a child together
+    pub fn is_forbidden_kin(
+        m1: &Mind,
+        m2: &Mind,
+        population: &Population,
+        culture: &CultureConfig,
+    ) -> bool {
+        return blood_kinship(m1, m2, population)
+            .and_then(|k| kinship_distance(&k))
+            .map(|d| d <= culture.forbidden_kinship_distance)
+            .unwrap_or(false);
+    }
+
+    fn gendered(gender: &Gender, female: &str, male: &str, neutral: &str) -> String {
+        return String::from(match gender {
+            Gender::Female => female,
+            Gender::Male => male,
+            Gender::Ambiguous => neutral,
+        });
+    }
+
+    fn greats(count: u32) -> String {
+        return "great-".repeat(count as usize);
+    }
+
+    fn ordinal(n: u32) -> String {
+        return match n {
+            1 => String::from("first"),
+            2 => String::from("second"),
+            3 => String::from("third"),
+            4 => String::from("fourth"),
+            5 => String::from("fifth"),
+            _ => format!("{}th", n),
+        };
+    }
+
+    fn removal(n: u32) -> String {
+        return match n {
+            0 => String::new(),
+            1 => String::from(" once removed"),
+            2 => String::from(" twice removed"),
+            _ => format!(" {} times removed", n),
+        };
+    }
+
+    // The kinship term for someone of the given gender, such as "great-aunt" or "second cousin once removed"
+    pub fn describe_kinship(kinship: &Kinship, gender: &Gender) -> String {
+        return match kinship {
+            Kinship::Themselves => String::from("self"),
+            Kinship::Ancestor(1) => gendered(gender, "mother", "father", "parent"),
+            Kinship::Ancestor(d) => format!(
+                "{}{}",
+                greats(d - 2),
+                gendered(gender, "grandmother", "grandfather", "grandparent")
+            ),
+            Kinship::Descendant(1) => gendered(gender, "daughter", "son", "child"),
+            Kinship::Descendant(d) => format!(
+                "{}{}",
+                greats(d - 2),
+                gendered(gender, "granddaughter", "grandson", "grandchild")
+            ),
+            Kinship::Sibling { half } => format!(
+                "{}{}",
+                if *half { "half-" } else { "" },
+                gendered(gender, "sister", "brother", "sibling")
+            ),
+            Kinship::Pibling(d) => format!(
+                "{}{}",
+                greats(d - 1),
+                gendered(gender, "aunt", "uncle", "pibling")
+            ),
+            Kinship::Nibling(d) => format!(
+                "{}{}",
+                greats(d - 1),
+                gendered(gender, "niece", "nephew", "nibling")
+            ),
+            Kinship::Cousin { degree, removal: r } => {
+                format!("{} cousin{}", ordinal(*degree), removal(*r))
+            }
+            Kinship::Spouse => gendered(gender, "wife", "husband", "spouse"),
+            Kinship::InLaw(term) => format!("{}-in-law", describe_kinship(term, gender)),
+        };
+    }
+
+    #[test]
+    fn test_kinship_terms() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let culture = random_culture(&dict, &None);
+        let mut population: Population = HashMap::default();
+        let mut add = |parent_ids: Vec<Uuid>, population: &mut Population| -> Uuid {
+            let mut mind = random_char(&dict, &culture, true);
+            mind.relations.clear();
+            for parent_id in parent_ids {
+                mind.relations.push((RelationVerb::Parent, parent_id));
+            }
+            let id = mind.id.clone();
+            population.insert(id.clone(), mind);
+            return id;
+        };
+        let grandmother = add(vec![], &mut population);
+        let grandfather = add(vec![], &mut population);
+        let aunt = add(vec![grandmother, grandfather], &mut population);
+        let father = add(vec![grandmother, grandfather], &mut population);
+        let half_uncle = add(vec![grandmother], &mut population);
+        let mother = add(vec![], &mut population);
+        let child = add(vec![father, mother], &mut population);
+        let cousin = add(vec![aunt], &mut population);
+        let cousins_child = add(vec![cousin], &mut population);
+        let grandchild = add(vec![child], &mut population);
+        population
+            .get_mut(&father)
+            .unwrap()
+            .relations
+            .push((RelationVerb::Spouse, mother));
+        population
+            .get_mut(&mother)
+            .unwrap()
+            .relations
+            .push((RelationVerb::Spouse, father));
+
+        let kin = |a: &Uuid, b: &Uuid| {
+            kinship(
+                population.get(a).unwrap(),
+                population.get(b).unwrap(),
+                &population,
+            )
+        };
+        assert_eq!(kin(&grandmother, &child), Some(Kinship::Ancestor(2)));
+        assert_eq!(kin(&grandchild, &grandfather), Some(Kinship::Descendant(3)));
+        assert_eq!(kin(&aunt, &father), Some(Kinship::Sibling { half: false }));
+        assert_eq!(
+            kin(&half_uncle, &father),
+            Some(Kinship::Sibling { half: true })
+        );
+        assert_eq!(kin(&aunt, &grandchild), Some(Kinship::Pibling(2)));
+        assert_eq!(
+            kin(&child, &cousin),
+            Some(Kinship::Cousin {
+                degree: 1,
+                removal: 0
+            })
+        );
+        assert_eq!(
+            kin(&cousins_child, &grandchild),
+            Some(Kinship::Cousin {
+                degree: 2,
+                removal: 0
+            })
+        );
+        assert_eq!(
+            kin(&cousins_child, &child),
+            Some(Kinship::Cousin {
+                degree: 1,
+                removal: 1
+            })
+        );
+        assert_eq!(kin(&mother, &father), Some(Kinship::Spouse));
+        assert_eq!(
+            kin(&aunt, &mother),
+            Some(Kinship::InLaw(Box::new(Kinship::Sibling { half: false })))
+        );
+        assert_eq!(
+            describe_kinship(&Kinship::Pibling(3), &Gender::Female),
+            "great-great-aunt"
+        );
+        assert_eq!(
+            describe_kinship(
+                &Kinship::Cousin {
+                    degree: 2,
+                    removal: 1
+                },
+                &Gender::Male
+            ),
+            "second cousin once removed"
+        );
+        assert_eq!(
+            describe_kinship(
+                &Kinship::InLaw(Box::new(Kinship::Ancestor(1))),
+                &Gender::Male
+            ),
+            "father-in-law"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+pub mod marriage {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::mind::mind::{
+        add_event_to_mind_log, add_romatic_event_to_mind_log, Gender, Mind,
+    };
+    use crate::city::population::mind::relations::family_by_marriage::family_by_marriage::update_family_by_marriage;
+    use crate::city::population::mind::relations::kinship::kinship::is_forbidden_kin;
+    use crate::city::population::mind::relations::parental_naming_formats::parental_naming_formats::get_new_couple_last_names;
+    use crate::city::population::mind::relations::partners::partners::{
+        is_single, mutually_compatible,
+    };
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::city::population::mind::relations::widowhood::widowhood::{
+        is_in_mourning, open_to_new_partner,
+    };
+    use crate::culture::marriage_customs::marriage_customs::{MarriageCustoms, MarriagePayment};
+    use crate::utils::utils::{thread_rng, HashMap, HashSet};
+
+    const ARRANGED_MAX_AGE_DIFF: u32 = 15;
+
+    // Whether the culture lets this couple wed at all
+    pub fn may_marry(m1: &Mind, m2: &Mind, customs: &MarriageCustoms) -> bool {
+        if m1.age < customs.min_marriage_age || m2.age < customs.min_marriage_age {
+            return false;
+        }
+        return customs.same_sex_marriage || !m1.gender.eq(&m2.gender);
+    }
+
+    fn spouse_count(mind: &Mind) -> usize {
+        return mind
+            .relations
+            .iter()
+            .filter(|(v, _id)| v.eq(&RelationVerb::Spouse))
+            .count();
+    }
+
+    // In polygamous cultures a married citizen may still take another spouse
+    fn can_take_spouse(mind: &Mind, customs: &MarriageCustoms) -> bool {
+        let partnered = mind
+            .relations
+            .iter()
+            .any(|(v, _id)| v.eq(&RelationVerb::Partner));
+        let spouses = spouse_count(mind);
+        return !partnered && spouses > 0 && spouses < customs.max_spouses;
+    }
+
+    fn has_living_parent(mind: &Mind, city: &City) -> bool {
+        return mind.relations.iter().any(|(v, id)| {
+            (v.eq(&RelationVerb::Parent) || v.eq(&RelationVerb::AdoptedParent))
+                && city.citizens.get(id).is_some_and(|p| p.alive)
+        });
+    }
+
+    fn household_budget(mind: &Mind, city: &City) -> i32 {
+        return mind
+            .household
+            .and_then(|id| city.households.get(&id))
+            .map(|h| h.budget)
+            .unwrap_or(0);
+    }
+
+    // Hands the dowry or bride price from one household to the other
+    pub fn pay_marriage_settlement(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        let customs = city.culture.marriage_customs.clone();
+        if customs.payment.eq(&MarriagePayment::None) {
+            return;
+        }
+        let m1 = city.citizens.get(id_1).unwrap();
+        let m2 = city.citizens.get(id_2).unwrap();
+        let (bride, groom) = match (&m1.gender, &m2.gender) {
+            (Gender::Female, Gender::Male) => (m1, m2),
+            (Gender::Male, Gender::Female) => (m2, m1),
+            _ => return,
+        };
+        let (payer, payee) = match customs.payment {
+            MarriagePayment::Dowry => (bride, groom),
+            _ => (groom, bride),
+        };
+        if payer.household.is_none()
+            || payee.household.is_none()
+            || payer.household.eq(&payee.household)
+        {
+            return;
+        }
+        let amount = (household_budget(payer, city) as f32 * customs.payment_share) as i32;
+        if amount < 1 {
+            return;
+        }
+        let (payer_id, payee_id) = (payer.id.clone(), payee.id.clone());
+        let (payer_household, payee_household) =
+            (payer.household.unwrap(), payee.household.unwrap());
+        city.households.get_mut(&payer_household).unwrap().budget -= amount;
+        city.households.get_mut(&payee_household).unwrap().budget += amount;
+        let event = match customs.payment {
+            MarriagePayment::Dowry => format!("brought a dowry of {} to the marriage", amount),
+            _ => format!("paid a bride price of {}", amount),
+        };
+        let year = city.year;
+        let logged_id = if customs.payment.eq(&MarriagePayment::Dowry) {
+            payer_id
+        } else {
+            payee_id
+        };
+        add_event_to_mind_log(city.citizens.get_mut(&logged_id).unwrap(), year, &event);
+    }
+
+    // Weds a couple chosen by their families, taking one another's names as the culture dictates
+    fn wed(city: &mut City, id_1: &Uuid, id_2: &Uuid) {
+        let m1 = city.citizens.get(id_1).unwrap().clone();
+        let m2 = city.citizens.get(id_2).unwrap().clone();
+        let (last_name_1, last_name_2) = get_new_couple_last_names(&m1, &m2, &city.culture);
+        let year = city.year;
+        for (mind, other, last_name) in [(&m1, &m2, last_name_1), (&m2, &m1, last_name_2)] {
+            let mind = city.citizens.get_mut(&mind.id).unwrap();
+            // a polygamous marriage keeps the family name already taken
+            if spouse_count(mind) < 1 {
+                mind.last_name = last_name;
+            }
+            mind.bereaved_in = None;
+            mind.relations
+                .push((RelationVerb::Spouse, other.id.clone()));
+            add_romatic_event_to_mind_log(
+                mind,
+                year,
+                RelationVerb::Spouse,
+                other,
+                "Was married by arrangement to",
+            );
+        }
+        update_family_by_marriage(city, &[id_1.clone(), id_2.clone()]);
+        pay_marriage_settlement(city, id_1, id_2);
+    }
+
+    // Everyone free to wed this year, grouped by age so a seeker only looks through their own generation
+    fn eligible_by_age(city: &City, customs: &MarriageCustoms) -> HashMap<u32, Vec<Uuid>> {
+        let mut by_age: HashMap<u32, Vec<Uuid>> = HashMap::default();
+        for c in city.citizens.values() {
+            if c.alive
+                && c.age >= customs.min_marriage_age
+                && ((is_single(c) && !is_in_mourning(c, city)) || can_take_spouse(c, customs))
+            {
+                by_age.entry(c.age).or_default().push(c.id);
+            }
+        }
+        return by_age;
+    }
+
+    // Parents find a match for their grown, single children, preferring families of similar means
+    pub fn arrange_marriages_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let customs = city.culture.marriage_customs.clone();
+        if customs.arranged_marriage_rate <= 0.0 {
+            return city;
+        }
+        let mut rng = thread_rng();
+        let mut seekers: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|c| {
+                c.alive
+                    && c.age >= customs.min_marriage_age
+                    && is_single(c)
+                    && has_living_parent(c, city)
+            })
+            .map(|c| c.id)
+            .filter(|_id| rng.gen::<f32>() < customs.arranged_marriage_rate)
+            .filter(|id| open_to_new_partner(city.citizens.get(id).unwrap(), city))
+            .collect();
+        seekers.shuffle(&mut rng);
+        let by_age = eligible_by_age(city, &customs);
+        let mut matched: HashSet<Uuid> = HashSet::default();
+        for seeker_id in seekers {
+            if matched.contains(&seeker_id) {
+                continue;
+            }
+            let seeker = city.citizens.get(&seeker_id).unwrap();
+            let budget = household_budget(seeker, city);
+            let ages = seeker.age.saturating_sub(ARRANGED_MAX_AGE_DIFF)
+                ..=seeker.age + ARRANGED_MAX_AGE_DIFF;
+            let mut candidates: Vec<&Mind> = ages
+                .filter_map(|age| by_age.get(&age))
+                .flatten()
+                .filter(|id| !id.eq(&&seeker_id) && !matched.contains(*id))
+                .map(|id| city.citizens.get(id).unwrap())
+                .filter(|c| mutually_compatible(seeker, c) && may_marry(seeker, c, &customs))
+                .collect();
+            candidates.shuffle(&mut rng);
+            candidates.sort_by_key(|c| household_budget(c, city).abs_diff(budget));
+            let spouse_id = candidates
+                .into_iter()
+                .find(|c| !is_forbidden_kin(seeker, c, &city.citizens, &city.culture))
+                .map(|c| c.id);
+            if spouse_id.is_some() {
+                matched.insert(seeker_id.clone());
+                matched.insert(spouse_id.unwrap());
+                wed(city, &seeker_id, &spouse_id.unwrap());
+            }
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_may_marry_and_can_take_spouse() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let culture = random_culture(&dict, &None);
+        let mut customs = culture.marriage_customs.clone();
+        customs.min_marriage_age = 16;
+        customs.same_sex_marriage = false;
+        customs.max_spouses = 1;
+        let mut m1 = random_char(&dict, &culture, true);
+        let mut m2 = random_char(&dict, &culture, true);
+        m1.relations.clear();
+        m2.relations.clear();
+        (m1.gender, m2.gender) = (Gender::Female, Gender::Male);
+        (m1.age, m2.age) = (16, 30);
+        assert!(may_marry(&m1, &m2, &customs));
+        m1.age = 15;
+        assert!(!may_marry(&m1, &m2, &customs));
+        assert!(!may_marry(&m2, &m1, &customs));
+        m1.age = 20;
+        m1.gender = Gender::Male;
+        assert!(!may_marry(&m1, &m2, &customs));
+        customs.same_sex_marriage = true;
+        assert!(may_marry(&m1, &m2, &customs));
+
+        // only the married may take a further spouse, and only up to the limit
+        assert!(!can_take_spouse(&m2, &customs));
+        m2.relations.push((RelationVerb::Spouse, m1.id));
+        assert!(!can_take_spouse(&m2, &customs));
+        customs.max_spouses = 3;
+        assert!(can_take_spouse(&m2, &customs));
+        m2.relations.push((RelationVerb::Spouse, Uuid::default()));
+        m2.relations.push((RelationVerb::Spouse, Uuid::default()));
+        assert!(!can_take_spouse(&m2, &customs));
+        m2.relations.pop();
+        m2.relations.push((RelationVerb::Partner, Uuid::default()));
+        assert!(!can_take_spouse(&m2, &customs));
+    }
+
+    #[test]
+    fn test_pay_marriage_settlement() {
+        use crate::city::city::new_city;
+        use crate::city::population::household::household::found_household;
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut city = new_city(String::new(), &random_culture(&dict, &None));
+        city.culture.marriage_customs.payment_share = 0.5;
+        let mut ids: Vec<Uuid> = Vec::new();
+        for gender in [Gender::Female, Gender::Male] {
+            let mut mind = random_char(&dict, &city.culture, true);
+            mind.relations.clear();
+            mind.gender = gender;
+            ids.push(mind.id.clone());
+            city.citizens.insert(mind.id.clone(), mind);
+        }
+        let (bride, groom) = (ids[0], ids[1]);
+        let bride_home = found_household(&mut city, &bride);
+        let groom_home = found_household(&mut city, &groom);
+        let budgets = |city: &City| {
+            return (
+                city.households.get(&bride_home).unwrap().budget,
+                city.households.get(&groom_home).unwrap().budget,
+            );
+        };
+        let reset = |city: &mut City| {
+            city.households.get_mut(&bride_home).unwrap().budget = 100;
+            city.households.get_mut(&groom_home).unwrap().budget = 100;
+        };
+
+        reset(&mut city);
+        city.culture.marriage_customs.payment = MarriagePayment::None;
+        pay_marriage_settlement(&mut city, &bride, &groom);
+        assert_eq!(budgets(&city), (100, 100));
+
+        city.culture.marriage_customs.payment = MarriagePayment::Dowry;
+        pay_marriage_settlement(&mut city, &groom, &bride);
+        assert_eq!(budgets(&city), (50, 150));
+
+        reset(&mut city);
+        city.culture.marriage_customs.payment = MarriagePayment::BridePrice;
+        pay_marriage_settlement(&mut city, &bride, &groom);
+        assert_eq!(budgets(&city), (150, 50));
+
+        // nothing changes hands within a single household
+        reset(&mut city);
+        city.citizens.get_mut(&bride).unwrap().household = Some(groom_home);
+        pay_marriage_settlement(&mut city, &bride, &groom);
+        assert_eq!(budgets(&city), (100, 100));
+    }
+}
@@ -3,6 +3,7 @@ pub mod parental_naming_formats {
     use rand::Rng;
     use regex::Regex;
 
+    use crate::utils::utils::thread_rng;
     use crate::{
         city::population::mind::mind::{Gender, Mind},
         culture::culture::CultureConfig,
@@ -56,8 +57,8 @@ pub mod parental_naming_formats {
 
             let mut m_last_name_split: Vec<&str> = m.1.split("-").collect();
             let mut f_last_name_split: Vec<&str> = f.1.split("-").collect();
-            m_last_name_split.shuffle(&mut rand::thread_rng());
-            f_last_name_split.shuffle(&mut rand::thread_rng());
+            m_last_name_split.shuffle(&mut thread_rng());
+            f_last_name_split.shuffle(&mut thread_rng());
 
             output.push_str(prefix.clone());
             match key.as_str() {
@@ -104,7 +105,7 @@ pub mod parental_naming_formats {
         culture: &CultureConfig,
     ) -> (String, String) {
         let mut formats = culture.parental_naming_formats.clone();
-        formats.shuffle(&mut rand::thread_rng());
+        formats.shuffle(&mut thread_rng());
         let (f1, f2, _cm, _cf) = formats.first().unwrap();
         return (
             parse_format(
@@ -142,9 +143,9 @@ pub mod parental_naming_formats {
         partner_f: &Mind,
         culture: &CultureConfig,
     ) -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut formats = culture.parental_naming_formats.clone();
-        formats.shuffle(&mut rand::thread_rng());
+        formats.shuffle(&mut thread_rng());
         let (_f1, _f2, cm, cf) = formats.first().unwrap();
         let mut target_format = if rng.gen::<f32>() > 0.5 { cm } else { cf };
         if gender.eq(&Gender::Male) {
@@ -1,40 +1,54 @@
 pub mod parents {
-    use std::collections::HashMap;
-
     use rand::seq::SliceRandom;
     use rand::Rng;
     use uuid::Uuid;
 
+    use crate::utils::utils::{thread_rng, HashMap};
     use crate::{
         city::{
             city::City,
             population::{
                 mind::{
                     appearance::appearance::generate_child_description,
+                    faith::faith::{adopt_faith, inherit_faith_id},
+                    genetics::genetics::inherit_genotype,
                     mind::{
-                        add_birth_to_mind_log, add_new_relation_to_mind_log, random_char, Mind,
+                        add_birth_to_mind_log, add_new_relation_to_mind_log,
+                        add_single_parent_birth_to_mind_log, random_char, Gender, Mind,
                     },
+                    naming::naming::{grandparent_names, random_middle_names},
+                    personality::personality::inherit_personality,
                     relations::{
+                        kinship::kinship::is_forbidden_kin,
                         parental_naming_formats::parental_naming_formats::get_child_last_name,
-                        partners::partners::TAKEN_VERBS,
+                        partners::partners::{is_single, TAKEN_VERBS},
                         relations::{link_family_at_birth, RelationVerb},
                     },
                 },
                 population::Population,
             },
         },
-        culture::culture::CultureConfig,
+        culture::{
+            culture::CultureConfig,
+            fertility::fertility::{fertility_at_age, FertilityProfile},
+            religion::religion::find_faith,
+        },
         language::language::Word,
     };
 
     const PARENT_PRESENCE_CHANCE: f32 = 0.3;
-    const MIN_CHILD_BEARING_AGE: u32 = 20;
-    const CHILD_LIMIT: usize = 3;
 
-    const SPOUSE_CHILD_CHANCE: f32 = 0.12;
-    const PARTNER_CHILD_CHANCE: f32 = 0.04;
+    // Births to unmarried couples are less common than within a marriage
+    const PARTNER_BIRTH_FACTOR: f32 = 0.9;
+    // Couples who already have the family they wanted still have the odd surprise
+    const UNPLANNED_BIRTH_FACTOR: f32 = 0.1;
+    // Past this age a father's fertility slowly declines
+    const PATERNAL_DECLINE_AGE: u32 = 45;
+    const PATERNAL_DECLINE_RATE: f32 = 0.03;
+    // Share of multiple births that are triplets rather than twins
+    const TRIPLET_SHARE: f32 = 0.02;
 
-    fn find_couples(population: &Population) -> Vec<(&Mind, &Mind)> {
+    pub fn find_couples(population: &Population) -> Vec<(&Mind, &Mind)> {
         let mut output: Vec<(&Mind, &Mind)> = Vec::new();
         for mind in population.values().filter(|c| c.alive) {
             let possible_partner_relation = mind
@@ -50,10 +64,11 @@ pub mod parents {
                     );
                 }
                 let partner = population.get(&partner_id).unwrap();
+                // in polygamous marriages one citizen can belong to several couples
                 let already_contained = output.iter().any(|(a, b)| {
                     let a_matches = a.id.eq(&mind.id) || a.id.eq(&partner_id);
                     let b_matches = b.id.eq(&mind.id) || b.id.eq(&partner_id);
-                    return a_matches || b_matches;
+                    return a_matches && b_matches;
                 });
                 if !already_contained {
                     output.push((mind, partner));
@@ -68,8 +83,9 @@ pub mod parents {
         mind: &Mind,
         population: &Population,
         lockout_ids: &Vec<Uuid>,
+        culture: &CultureConfig,
     ) -> Option<Vec<Uuid>> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         if rng.gen::<f32>() < PARENT_PRESENCE_CHANCE {
             return None;
         }
@@ -81,7 +97,7 @@ pub mod parents {
         filtered_parents.retain(|_id, c| !lockout_ids.contains(&c.id));
         let mut potential_parents = find_couples(&filtered_parents);
         potential_parents.shuffle(&mut rng);
-        let target_age_range = (mind.age + MIN_CHILD_BEARING_AGE)..(u32::MAX);
+        let target_age_range = (mind.age + culture.fertility.min_age)..(u32::MAX);
         return potential_parents
             .iter()
             .find(|(a, b)| {
@@ -90,16 +106,29 @@ pub mod parents {
             .map(|(a, b)| vec![a.id, b.id]);
     }
 
-    fn get_lockout_parents(input: &Vec<(Uuid, Vec<Uuid>)>) -> Vec<Uuid> {
+    // Couples who already have as many children as they wanted take no more
+    fn get_lockout_parents(
+        input: &Vec<(Uuid, Vec<Uuid>)>,
+        population: &Population,
+        culture: &CultureConfig,
+    ) -> Vec<Uuid> {
         let mut output: Vec<Uuid> = Vec::new();
-        let mut frequency_table: HashMap<Uuid, usize> = HashMap::new();
+        let mut frequency_table: HashMap<Uuid, usize> = HashMap::default();
         for (_, parents) in input {
+            let child_limit = match parents.as_slice() {
+                [a, b] => desired_children(
+                    population.get(a).unwrap(),
+                    population.get(b).unwrap(),
+                    &culture.fertility,
+                ),
+                _ => culture.fertility.desired_children as usize,
+            };
             for id in parents {
                 frequency_table
                     .entry(*id)
                     .and_modify(|i| *i += 1)
                     .or_insert(1);
-                if frequency_table.get(&id).unwrap() >= &CHILD_LIMIT && !output.contains(&id) {
+                if frequency_table.get(&id).unwrap() >= &child_limit && !output.contains(&id) {
                     for id_all in parents {
                         output.push(*id_all);
                     }
@@ -124,9 +153,10 @@ pub mod parents {
                 .values()
                 .find(|c| c.alive && c.id.eq(&mind_id))
                 .unwrap();
-            let lockout_ids = get_lockout_parents(&relations_to_add);
+            let lockout_ids = get_lockout_parents(&relations_to_add, &city.citizens, &city.culture);
             // println!("{:#?}", lockout_ids);
-            let possible_parents = find_parent_ids(mind, &city.citizens, &lockout_ids);
+            let possible_parents =
+                find_parent_ids(mind, &city.citizens, &lockout_ids, &city.culture);
             if possible_parents.is_some() {
                 let parents = possible_parents.unwrap();
                 let lockout_failed = parents.iter().any(|p| lockout_ids.contains(p));
@@ -154,37 +184,146 @@ pub mod parents {
         return city;
     }
 
-    fn couple_will_bear(m1: &Mind, m2: &Mind, culture: &CultureConfig) -> bool {
-        if m1.age < culture.adult_age || m2.age < culture.adult_age {
-            return false;
-        }
-        if m1.gender.eq(&m2.gender) {
-            return false;
+    pub fn bearer<'a>(m1: &'a Mind, m2: &'a Mind) -> (&'a Mind, &'a Mind) {
+        if m1.gender.eq(&Gender::Male) {
+            return (m2, m1);
         }
-        let mut rng = rand::thread_rng();
-        let (verb, _id) = m1
+        return (m1, m2);
+    }
+
+    fn shared_children(m1: &Mind, m2: &Mind) -> usize {
+        return m1
             .relations
             .iter()
-            .find(|(v, _id)| TAKEN_VERBS.contains(&v))
-            .unwrap();
-        let m1_child_count = m1
+            .filter(|(v, _id)| v.eq(&RelationVerb::Child) || v.eq(&RelationVerb::AdoptedChild))
+            .filter(|(_v, id)| {
+                m2.relations.iter().any(|(v2, id2)| {
+                    id2.eq(id)
+                        && (v2.eq(&RelationVerb::Child) || v2.eq(&RelationVerb::AdoptedChild))
+                })
+            })
+            .count();
+    }
+
+    // Each couple settles on its own family size, fixed for as long as they are together
+    pub fn desired_children(m1: &Mind, m2: &Mind, profile: &FertilityProfile) -> usize {
+        let offsets = [-2, -1, 0, 0, 1];
+        let seed = ((m1.id.as_u128() ^ m2.id.as_u128()) % offsets.len() as u128) as usize;
+        return (profile.desired_children as i32 + offsets[seed]).max(0) as usize;
+    }
+
+    pub fn couple_birth_chance(
+        m1: &Mind,
+        m2: &Mind,
+        population: &Population,
+        culture: &CultureConfig,
+    ) -> f32 {
+        if m1.age < culture.adult_age || m2.age < culture.adult_age {
+            return 0.0;
+        }
+        if m1.gender.eq(&m2.gender) || !m1.fertile || !m2.fertile {
+            return 0.0;
+        }
+        if is_forbidden_kin(m1, m2, population, culture) {
+            return 0.0;
+        }
+        let married = m1
             .relations
             .iter()
-            .filter(|(v, _id)| v.eq(&RelationVerb::Child))
-            .count();
-        let m2_child_count = m2
+            .any(|(v, id)| v.eq(&RelationVerb::Spouse) && id.eq(&m2.id));
+        let (mother, father) = bearer(m1, m2);
+        let mut chance = fertility_at_age(mother.age, &culture.fertility);
+        if father.gender.eq(&Gender::Male) && father.age > PATERNAL_DECLINE_AGE {
+            chance *=
+                (1.0 - (father.age - PATERNAL_DECLINE_AGE) as f32 * PATERNAL_DECLINE_RATE).max(0.0);
+        }
+        if !married {
+            chance *= PARTNER_BIRTH_FACTOR;
+        }
+        // wives in a polygamous marriage each have fewer children
+        let spouses = father
             .relations
             .iter()
-            .filter(|(v, _id)| v.eq(&RelationVerb::Child))
+            .filter(|(v, _id)| v.eq(&RelationVerb::Spouse))
             .count();
+        if spouses > 1 {
+            chance /= spouses as f32;
+        }
+        if shared_children(m1, m2) >= desired_children(m1, m2, &culture.fertility) {
+            chance *= UNPLANNED_BIRTH_FACTOR;
+        }
+        return chance;
+    }
 
-        let base_chance = if verb.eq(&RelationVerb::Spouse) {
-            SPOUSE_CHILD_CHANCE
-        } else {
-            PARTNER_CHILD_CHANCE
+    fn multiple_birth_count(profile: &FertilityProfile) -> usize {
+        let mut rng = thread_rng();
+        let roll = rng.gen::<f32>();
+        if roll < profile.twin_rate * TRIPLET_SHARE {
+            return 3;
+        }
+        if roll < profile.twin_rate {
+            return 2;
+        }
+        return 1;
+    }
+
+    // Creates and links a newborn; without a second parent the other half of the inheritance is a stranger's
+    pub fn bear_child(
+        city: &mut City,
+        dict: &Vec<Word>,
+        parent_1: &Mind,
+        parent_2: Option<&Mind>,
+    ) -> Uuid {
+        let stranger = random_char(&dict, &city.culture, false);
+        let other_parent = parent_2.unwrap_or(&stranger);
+        let mut child = random_char(&dict, &city.culture, false);
+        child.age = 1;
+        child.last_name = match parent_2 {
+            Some(parent_2) => get_child_last_name(&child.gender, parent_1, parent_2, &city.culture),
+            None => parent_1.last_name.clone(),
+        };
+        child.middle_names = random_middle_names(
+            &dict,
+            &child.gender,
+            &city.culture.era,
+            &grandparent_names(&child.gender, parent_1, parent_2.unwrap_or(parent_1), &city),
+        );
+        let faith_id = inherit_faith_id(parent_1, parent_2.unwrap_or(parent_1));
+        if faith_id.is_some() {
+            let faith = find_faith(&city.culture.faiths, &faith_id.unwrap());
+            adopt_faith(&mut child, faith.unwrap());
+        }
+        child.genotype = inherit_genotype(&parent_1.genotype, &other_parent.genotype);
+        child.personality = inherit_personality(&parent_1.personality, &other_parent.personality);
+        child.physical_description = generate_child_description(
+            &dict,
+            &child.genotype,
+            &city.culture.era,
+            &parent_1.physical_description,
+            &other_parent.physical_description,
+        );
+
+        let parents: Vec<&Mind> = vec![Some(parent_1), parent_2]
+            .into_iter()
+            .flatten()
+            .collect();
+        for parent in parents.iter() {
+            let parent_mut = city.citizens.get_mut(&parent.id).unwrap();
+            parent_mut
+                .relations
+                .push((RelationVerb::Child, child.id.clone()));
+            add_new_relation_to_mind_log(parent_mut, city.year, RelationVerb::Child, &child);
+            child
+                .relations
+                .push((RelationVerb::Parent, parent.id.clone()));
+        }
+        match parent_2 {
+            Some(parent_2) => add_birth_to_mind_log(&mut child, city.year, parent_1, parent_2),
+            None => add_single_parent_birth_to_mind_log(&mut child, city.year, parent_1),
         };
-        let chance = base_chance / ((m1_child_count + m2_child_count) as f32 / 2.0);
-        return rng.gen::<f32>() < chance;
+        link_family_at_birth(city, &mut child);
+        city.citizens.insert(child.id.clone(), child.clone());
+        return child.id;
     }
 
     pub fn generate_children_per_year<'a>(
@@ -192,46 +331,61 @@ pub mod parents {
         culture: &CultureConfig,
         dict: &Vec<Word>,
     ) -> &'a mut City {
+        let mut rng = thread_rng();
         let citizen_ref = city.citizens.clone();
         let couples = find_couples(&citizen_ref);
 
         for (m1, m2) in couples {
-            if couple_will_bear(m1, m2, &culture) {
-                // let mut citizens = city.citizens.values_mut().filter(|c| c.alive);
-                let pm1 = citizen_ref.get(&m1.id);
-                let pm2 = citizen_ref.get(&m2.id);
-                if pm1.is_some() && pm2.is_some() {
-                    let mut child = random_char(&dict, &city.culture.era, false);
-                    child.age = 1;
-                    child.last_name = get_child_last_name(&child.gender, m1, m2, &city.culture);
-                    child.physical_description = generate_child_description(
-                        &dict,
-                        &m1.physical_description,
-                        &m2.physical_description,
-                    );
-
-                    let mind_1 = city.citizens.get_mut(&m1.id).unwrap();
-                    mind_1
-                        .relations
-                        .push((RelationVerb::Child, child.id.clone()));
-                    add_new_relation_to_mind_log(mind_1, city.year, RelationVerb::Child, &child);
-                    drop(mind_1);
-
-                    let mind_2 = city.citizens.get_mut(&m2.id).unwrap();
-                    mind_2
-                        .relations
-                        .push((RelationVerb::Child, child.id.clone()));
-                    add_new_relation_to_mind_log(mind_2, city.year, RelationVerb::Child, &child);
-                    drop(mind_2);
-
-                    child.relations.push((RelationVerb::Parent, m1.id.clone()));
-                    child.relations.push((RelationVerb::Parent, m2.id.clone()));
-                    add_birth_to_mind_log(&mut child, city.year, pm1.unwrap(), pm2.unwrap());
-                    link_family_at_birth(city, &mut child);
-                    city.citizens.insert(child.id.clone(), child.clone());
+            if rng.gen::<f32>() < couple_birth_chance(m1, m2, &citizen_ref, &culture) {
+                for _i in 0..multiple_birth_count(&culture.fertility) {
+                    bear_child(city, dict, m1, Some(m2));
+                }
+            }
+        }
+        // single citizens occasionally have a child they raise alone
+        let single_parents: Vec<&Mind> = citizen_ref
+            .values()
+            .filter(|c| {
+                c.alive
+                    && c.fertile
+                    && !c.gender.eq(&Gender::Male)
+                    && c.age >= culture.adult_age
+                    && is_single(c)
+            })
+            .collect();
+        for parent in single_parents {
+            let chance = fertility_at_age(parent.age, &culture.fertility)
+                * culture.fertility.single_parent_rate;
+            if rng.gen::<f32>() < chance {
+                for _i in 0..multiple_birth_count(&culture.fertility) {
+                    bear_child(city, dict, parent, None);
                 }
             }
         }
         return city;
     }
+
+    #[test]
+    fn test_desired_children() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let culture = random_culture(&dict, &None);
+        let mut profile = culture.fertility.clone();
+        profile.desired_children = 3;
+        let m1 = random_char(&dict, &culture, true);
+        for _i in 0..20 {
+            let m2 = random_char(&dict, &culture, true);
+            let desired = desired_children(&m1, &m2, &profile);
+            assert!((1..=4).contains(&desired));
+            // the goal belongs to the couple, whichever way round they are asked
+            assert_eq!(desired, desired_children(&m2, &m1, &profile));
+            assert_eq!(desired, desired_children(&m1, &m2, &profile));
+        }
+        profile.desired_children = 1;
+        let m2 = random_char(&dict, &culture, true);
+        assert!(desired_children(&m1, &m2, &profile) <= 2);
+    }
 }
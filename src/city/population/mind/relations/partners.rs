@@ -1,6 +1,6 @@
 pub mod partners {
     use rand::seq::SliceRandom;
-    use std::{collections::HashMap, ops::Range};
+    use std::ops::Range;
 
     use rand::Rng;
     use uuid::Uuid;
@@ -8,22 +8,37 @@ pub mod partners {
     use crate::city::{
         city::City,
         population::{
+            household::household::found_household,
             mind::{
                 mind::*,
                 relations::{
+                    affinity::affinity::{
+                        default_affinity, get_affinity, romantic_split_modifier, set_affinity,
+                        update_affinity,
+                    },
+                    custody::custody::settle_custody,
+                    family_by_marriage::family_by_marriage::update_family_by_marriage,
                     friends::friends::SOCIAL_RELATIONS,
+                    grudges::grudges::{add_grudge, GrudgeCause},
+                    kinship::kinship::is_forbidden_kin,
+                    marriage::marriage::{may_marry, pay_marriage_settlement},
                     parental_naming_formats::parental_naming_formats::get_new_couple_last_names,
                     relations::*,
+                    widowhood::widowhood::open_to_new_partner,
                 },
             },
             population::Population,
         },
     };
+    use crate::culture::culture::CultureConfig;
+    use crate::utils::utils::{thread_rng, HashMap};
 
     const PARTNER_CHANCE_GENERAL: f32 = 0.3; // multiple annual chances
     const PARTNER_MARRIAGE_RATE: f32 = 0.075; // single anunal chance
     const PARTNER_SPLIT_RATE: f32 = 0.06; // single annual chance
     const MARRIAGE_SPLIT_RATE: f32 = 0.03; // single annual chance
+    const BREAKUP_GRUDGE_CHANCE: f32 = 0.25;
+    const DIVORCE_GRUDGE_CHANCE: f32 = 0.4;
 
     const MAX_RELATION_AGE_DIFF: u32 = 20;
     pub const TAKEN_VERBS: [RelationVerb; 2] = [RelationVerb::Partner, RelationVerb::Spouse];
@@ -39,7 +54,7 @@ pub mod partners {
     }
 
     fn get_partner_verb() -> RelationVerb {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let married = rng.gen::<f32>() < PARTNER_MARRIAGE_RATE;
         let split = rng.gen::<f32>() < PARTNER_SPLIT_RATE;
         let verb: RelationVerb;
@@ -59,29 +74,24 @@ pub mod partners {
         return verb;
     }
 
-    fn is_single(mind: &Mind) -> bool {
+    pub fn is_single(mind: &Mind) -> bool {
         return TAKEN_VERBS
             .iter()
             .all(|v| !mind.relations.iter().any(|(mv, _id)| mv.eq(&v)));
     }
 
-    fn compatible_sexuality(input: &Sexuality) -> Vec<Sexuality> {
-        return match input {
-            &Sexuality::Homosexual => vec![Sexuality::Homosexual, Sexuality::Bisexual],
-            &Sexuality::Bisexual => vec![Sexuality::Homosexual, Sexuality::Bisexual],
-            &Sexuality::Hetrosexual => vec![Sexuality::Hetrosexual],
-            _ => Vec::new(),
+    fn is_attracted_to(mind: &Mind, target: &Mind) -> bool {
+        return match mind.sexuality {
+            Sexuality::Hetrosexual => !mind.gender.eq(&target.gender),
+            Sexuality::Homosexual => mind.gender.eq(&target.gender),
+            Sexuality::Bisexual => true,
+            // treated as aromantic, so never seeks or accepts a romantic partner
+            Sexuality::Asexual => false,
         };
     }
 
-    fn determine_partner_gender(mind: &Mind) -> Gender {
-        if mind.sexuality.eq(&Sexuality::Hetrosexual) {
-            return invert_gender(&mind.gender);
-        } else if mind.sexuality.eq(&Sexuality::Homosexual) {
-            return mind.gender.clone();
-        } else {
-            return invert_gender(&Gender::Ambiguous);
-        }
+    pub fn mutually_compatible(m1: &Mind, m2: &Mind) -> bool {
+        return is_attracted_to(m1, m2) && is_attracted_to(m2, m1);
     }
 
     fn determine_age_range(mind: &Mind, max_age_gap: u32) -> Range<u32> {
@@ -95,50 +105,49 @@ pub mod partners {
     }
 
     fn search_for_partner<'a>(
+        mind: &Mind,
         population: &'a Population,
-        target_gender: &Gender,
         age_range: Range<u32>,
-        compatible_sexualities: Vec<Sexuality>,
         to_ignore: &Vec<Uuid>,
+        kin: &Population,
+        culture: &CultureConfig,
     ) -> Option<&'a Mind> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut filtered: Vec<&Mind> = population
             .values()
             .filter(|c| is_single(&c))
-            .filter(|c| c.gender.eq(&target_gender))
             .filter(|c| age_range.contains(&c.age))
             .filter(|c| {
                 return !to_ignore.iter().any(|id| id.eq(&c.id));
             })
-            .filter(|c| compatible_sexualities.contains(&c.sexuality))
+            .filter(|c| mutually_compatible(&mind, &c))
             .collect();
         filtered.shuffle(&mut rng);
         if rng.gen::<f32>() > PARTNER_CHANCE_GENERAL {
             return None;
         }
-        for mind in filtered {
-            return Some(mind);
-        }
-        return None;
+        // kinship is only worked out for candidates who pass the cheaper checks
+        return filtered
+            .into_iter()
+            .find(|c| !is_forbidden_kin(mind, c, kin, culture));
     }
 
     fn find_partner_id(
         mind: &Mind,
         population: &Population,
         to_ignore: &Vec<Uuid>,
+        kin: &Population,
+        culture: &CultureConfig,
     ) -> Option<Uuid> {
-        let mut rng = rand::thread_rng();
-        let target_gender = determine_partner_gender(&mind);
+        let mut rng = thread_rng();
+        if mind.sexuality.eq(&Sexuality::Asexual) {
+            return None;
+        }
         let range_roll = rng.gen::<f32>();
         for i in 0..MAX_RELATION_AGE_DIFF {
             let age_range = determine_age_range(&mind, (range_roll * i as f32) as u32);
-            let possible_partner = search_for_partner(
-                population,
-                &target_gender,
-                age_range,
-                compatible_sexuality(&mind.sexuality),
-                to_ignore,
-            );
+            let possible_partner =
+                search_for_partner(&mind, population, age_range, to_ignore, kin, culture);
             if possible_partner.is_some() {
                 return Some(possible_partner.unwrap().id);
             }
@@ -164,7 +173,13 @@ pub mod partners {
 
                 let mut taken_list = flatten_rel_map(&relations_to_add);
                 taken_list.push(mind.id.clone());
-                let possible_partner_id = find_partner_id(&mind, &city.citizens, &taken_list);
+                let possible_partner_id = find_partner_id(
+                    &mind,
+                    &city.citizens,
+                    &taken_list,
+                    &city.citizens,
+                    &city.culture,
+                );
                 if possible_partner_id.is_some() {
                     let root_repeating = flatten_rel_map(&relations_to_add)
                         .iter()
@@ -185,7 +200,7 @@ pub mod partners {
         // let used_ids: Vec<Uuid> = Vec::new();
         // relations_to_add = relations_to_add.iter().collect();
 
-        for (id_1, id_2) in relations_to_add {
+        for (id_1, id_2) in relations_to_add.iter().cloned() {
             let citizens = city.citizens.values_mut().filter(|c| c.alive);
             let mut mind_1: Option<&mut Mind> = None;
             let mut mind_2: Option<&mut Mind> = None;
@@ -210,19 +225,23 @@ pub mod partners {
                 println!("Mind Lookup Failed");
             }
         }
+        for (id_1, id_2) in relations_to_add {
+            update_family_by_marriage(city, &[id_1, id_2]);
+        }
         return city;
     }
 
     pub fn link_partners_by_year<'a>(city: &'a mut City) -> &'a mut City {
-        let citizen_ids: Vec<Uuid> = city
+        let (citizen_ids, not_looking): (Vec<Uuid>, Vec<Uuid>) = city
             .citizens
             .values()
             .filter(|c| c.alive && is_single(c))
             .map(|c| c.id)
-            .collect();
+            .partition(|id| open_to_new_partner(city.citizens.get(id).unwrap(), city));
 
         let mut relations_to_add: Vec<(Uuid, Uuid)> = Vec::new();
-        let mut taken_list = Vec::new();
+        // those still mourning are neither looking nor approached
+        let mut taken_list = not_looking;
 
         for mind_id in citizen_ids {
             if !flatten_rel_map(&relations_to_add).contains(&mind_id) {
@@ -237,7 +256,7 @@ pub mod partners {
                     .map(|(_v, id)| id)
                     .collect();
 
-                let mut friends: HashMap<Uuid, Mind> = HashMap::new();
+                let mut friends: HashMap<Uuid, Mind> = HashMap::default();
                 for id in friend_ids {
                     friends.insert(id.clone(), city.citizens.get(&id).unwrap().clone());
                 }
@@ -246,6 +265,8 @@ pub mod partners {
                     &mind,
                     &friends,
                     &vec![taken_list.clone(), vec![mind.id.clone()]].concat(),
+                    &city.citizens,
+                    &city.culture,
                 );
                 if possible_partner_id.is_some() {
                     let root_repeating = taken_list.iter().any(|c| c.eq(&mind.id));
@@ -268,11 +289,20 @@ pub mod partners {
         for (id_1, id_2) in relations_to_add {
             let m1 = city.citizens.get(&id_1).unwrap().clone();
             let m2 = city.citizens.get(&id_2).unwrap().clone();
+            let friendship = m1.affinities.get(&id_2).cloned().unwrap_or(0);
+            set_affinity(
+                city,
+                &id_1,
+                &id_2,
+                friendship.max(default_affinity(&RelationVerb::Partner)),
+            );
             let mind_1 = city.citizens.get_mut(&id_1).unwrap();
+            mind_1.bereaved_in = None;
             mind_1.relations.push((RelationVerb::Partner, id_2.clone()));
             add_romatic_event_to_mind_log(mind_1, city.year, RelationVerb::Partner, &m2, "Gained");
             drop(mind_1);
             let mind_2 = city.citizens.get_mut(&id_2).unwrap();
+            mind_2.bereaved_in = None;
             mind_2.relations.push((RelationVerb::Partner, id_1.clone()));
             add_romatic_event_to_mind_log(mind_2, city.year, RelationVerb::Partner, &m1, "Gained");
             drop(mind_2);
@@ -281,14 +311,38 @@ pub mod partners {
     }
 
     pub fn update_partners_by_year<'a>(city: &'a mut City) -> &'a mut City {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let citizen_ids: Vec<Uuid> = city
             .citizens
             .values()
             .filter(|c| c.alive)
             .map(|c| c.id)
             .collect();
+        let mut splits: Vec<(Uuid, Uuid, GrudgeCause, f32)> = Vec::new();
+        let mut leavers: Vec<(Uuid, Uuid)> = Vec::new();
+        let mut marriage_changes: Vec<(Uuid, Uuid)> = Vec::new();
+        let mut weddings: Vec<(Uuid, Uuid)> = Vec::new();
         for id in citizen_ids {
+            let couple = city
+                .citizens
+                .get(&id)
+                .unwrap()
+                .relations
+                .iter()
+                .find(|(v, _rid)| TAKEN_VERBS.contains(&v))
+                .cloned();
+            // couples grow closer or further apart before deciding whether to stay together
+            let split_modifier = match couple {
+                Some((verb, partner_id)) if city.citizens.get(&partner_id).unwrap().alive => {
+                    let score = if id < partner_id {
+                        update_affinity(city, &verb, &id, &partner_id)
+                    } else {
+                        get_affinity(city.citizens.get(&id).unwrap(), &verb, &partner_id)
+                    };
+                    romantic_split_modifier(score, &verb)
+                }
+                _ => 1.0,
+            };
             let mut citizens = city.citizens.values_mut().filter(|c| c.alive);
             let mind = citizens.find(|c| c.id.eq(&id)).unwrap();
             if !is_single(mind) {
@@ -302,7 +356,13 @@ pub mod partners {
                     let partner = p.unwrap();
                     match verb {
                         RelationVerb::Partner => {
-                            if rng.gen::<f32>() < PARTNER_SPLIT_RATE {
+                            if rng.gen::<f32>() < PARTNER_SPLIT_RATE * split_modifier {
+                                splits.push((
+                                    mind.id.clone(),
+                                    partner_id.clone(),
+                                    GrudgeCause::Breakup,
+                                    BREAKUP_GRUDGE_CHANCE * split_modifier,
+                                ));
                                 mind.relations
                                     .retain(|(v, id)| !(v.eq(&verb) && id.eq(&partner_id)));
                                 mind.relations
@@ -314,6 +374,11 @@ pub mod partners {
                                     partner,
                                     "Broke up with",
                                 );
+                                if rng.gen::<f32>() < 0.5 {
+                                    leavers.push((mind.id.clone(), partner.id.clone()));
+                                } else {
+                                    leavers.push((partner.id.clone(), mind.id.clone()));
+                                }
 
                                 partner
                                     .relations
@@ -328,9 +393,8 @@ pub mod partners {
                                     mind,
                                     "Broke up with",
                                 );
-                            } else if mind.age > ADULT_AGE_FROM
-                                && partner.age > ADULT_AGE_FROM
-                                && rng.gen::<f32>() < PARTNER_MARRIAGE_RATE
+                            } else if may_marry(mind, partner, &city.culture.marriage_customs)
+                                && rng.gen::<f32>() < city.culture.marriage_customs.marriage_rate
                             {
                                 let (mind_last_name, partner_last_name) =
                                     get_new_couple_last_names(&mind, &partner, &city.culture);
@@ -361,10 +425,18 @@ pub mod partners {
                                     mind,
                                     "Married",
                                 );
+                                marriage_changes.push((mind.id.clone(), partner.id.clone()));
+                                weddings.push((mind.id.clone(), partner.id.clone()));
                             }
                         }
                         RelationVerb::Spouse => {
-                            if rng.gen::<f32>() < MARRIAGE_SPLIT_RATE {
+                            if rng.gen::<f32>() < MARRIAGE_SPLIT_RATE * split_modifier {
+                                splits.push((
+                                    mind.id.clone(),
+                                    partner_id.clone(),
+                                    GrudgeCause::Divorce,
+                                    DIVORCE_GRUDGE_CHANCE * split_modifier,
+                                ));
                                 let mind_left = rng.gen::<f32>() < 0.5;
                                 mind.relations
                                     .retain(|(v, id)| !(v.eq(&verb) && id.eq(&partner_id)));
@@ -377,10 +449,11 @@ pub mod partners {
                                     partner,
                                     "Broke up with",
                                 );
+                                marriage_changes.push((mind.id.clone(), partner.id.clone()));
                                 if mind_left {
-                                    mind.residence = None;
+                                    leavers.push((mind.id.clone(), partner.id.clone()));
                                 } else {
-                                    partner.residence = None;
+                                    leavers.push((partner.id.clone(), mind.id.clone()));
                                 }
 
                                 partner
@@ -403,6 +476,132 @@ pub mod partners {
                 }
             }
         }
+        for (id_1, id_2) in marriage_changes {
+            update_family_by_marriage(city, &[id_1, id_2]);
+        }
+        for (id_1, id_2) in weddings {
+            pay_marriage_settlement(city, &id_1, &id_2);
+        }
+        // one of a cohabiting couple moves out, and the children go to whichever parent gains custody
+        for (id, ex_id) in leavers {
+            let household = city.citizens.get(&id).unwrap().household;
+            let shared_household =
+                household.filter(|_h| household.eq(&city.citizens.get(&ex_id).unwrap().household));
+            if shared_household.is_some() {
+                found_household(city, &id);
+            }
+            settle_custody(city, &ex_id, &id, shared_household);
+        }
+        // the less fondly a couple parted, the likelier one of them holds it against the other
+        for (id_1, id_2, cause, chance) in splits {
+            if rng.gen::<f32>() < chance {
+                add_grudge(city, &id_1, &id_2, cause);
+            }
+        }
         return city;
     }
+
+    #[test]
+    fn test_partners_are_not_close_kin() {
+        use crate::city::population::mind::relations::parents::parents::couple_birth_chance;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut culture = random_culture(&dict, &None);
+        // first cousins and closer may not pair off
+        culture.forbidden_kinship_distance = 4;
+        let mut population: Population = Population::default();
+        let mut add = |gender: Gender, age: u32, parent_ids: Vec<Uuid>| -> Uuid {
+            let mut mind = random_char(&dict, &culture, true);
+            mind.relations.clear();
+            mind.gender = gender;
+            mind.age = age;
+            mind.sexuality = Sexuality::Hetrosexual;
+            mind.fertile = true;
+            for parent_id in parent_ids {
+                mind.relations.push((RelationVerb::Parent, parent_id));
+            }
+            let id = mind.id.clone();
+            population.insert(id.clone(), mind);
+            return id;
+        };
+        let grandmother = add(Gender::Female, 64, vec![]);
+        let grandfather = add(Gender::Male, 66, vec![]);
+        let aunt = add(Gender::Female, 52, vec![grandmother, grandfather]);
+        let father = add(Gender::Male, 55, vec![grandmother, grandfather]);
+        let mother = add(Gender::Female, 54, vec![]);
+        let daughter = add(Gender::Female, 28, vec![father, mother]);
+        let brother = add(Gender::Male, 30, vec![father, mother]);
+        let cousin = add(Gender::Male, 27, vec![aunt]);
+        let stranger = add(Gender::Male, 29, vec![]);
+        for (id_1, id_2) in [(grandmother, grandfather), (father, mother)] {
+            for (id, other_id) in [(id_1, id_2), (id_2, id_1)] {
+                population
+                    .get_mut(&id)
+                    .unwrap()
+                    .relations
+                    .push((RelationVerb::Spouse, other_id));
+            }
+        }
+
+        // the brother and cousin are the right age and single, but only the stranger is a match
+        let mind = population.get(&daughter).unwrap();
+        for _i in 0..50 {
+            let partner_id =
+                find_partner_id(mind, &population, &vec![daughter], &population, &culture);
+            assert!(partner_id.is_none() || partner_id.unwrap().eq(&stranger));
+        }
+        // nor will kin raise a child together
+        let chance = |id: &Uuid| {
+            couple_birth_chance(mind, population.get(id).unwrap(), &population, &culture)
+        };
+        assert_eq!(chance(&brother), 0.0);
+        assert_eq!(chance(&cousin), 0.0);
+        assert!(chance(&stranger) > 0.0);
+    }
+
+    #[test]
+    fn test_seeded_simulation_has_no_kin_couples() {
+        use crate::city::city::simulate;
+        use crate::language::language::Era;
+        use crate::utils::utils::seed_rng;
+
+        // a fixed seed makes any violation repeatable
+        seed_rng(45);
+        let city = simulate(150, 80, Some(Era::Modern));
+        let couple_verbs = [
+            RelationVerb::Partner,
+            RelationVerb::Spouse,
+            RelationVerb::ExPartner,
+            RelationVerb::ExSpouse,
+            RelationVerb::LatePartner,
+            RelationVerb::LateSpouse,
+        ];
+        let forbidden = |id_1: &Uuid, id_2: &Uuid| {
+            let m1 = city.citizens.get(id_1).unwrap();
+            let m2 = city.citizens.get(id_2).unwrap();
+            return is_forbidden_kin(m1, m2, &city.citizens, &city.culture);
+        };
+        for mind in city.citizens.values() {
+            for (verb, id) in mind.relations.iter() {
+                if couple_verbs.contains(verb) {
+                    assert!(!forbidden(&mind.id, id), "{} paired with kin", mind.id);
+                }
+            }
+            let parents: Vec<&Uuid> = mind
+                .relations
+                .iter()
+                .filter(|(v, _id)| v.eq(&RelationVerb::Parent))
+                .map(|(_v, id)| id)
+                .collect();
+            if parents.len() == 2 {
+                assert!(
+                    !forbidden(parents[0], parents[1]),
+                    "{} born to kin",
+                    mind.id
+                );
+            }
+        }
+    }
 }
@@ -1,131 +1,469 @@
 pub mod residences {
-    use rand::seq::SliceRandom;
     use rand::Rng;
     use uuid::Uuid;
 
-    use crate::city::building::building::BuildingFloorArea;
+    use crate::city::building::building::{FloorAreaType, Landlord};
     use crate::city::city::City;
-    use crate::city::population::mind::mind::{add_residence_to_mind_log, Mind};
-    use crate::city::population::mind::relations::relations::{
-        find_relation, RelationVerb, ADULT_AGE_FROM,
+    use crate::city::population::household::household::{
+        find_household, household_surplus, set_dwelling, Household,
     };
+    use crate::city::population::mind::mind::{add_residence_to_mind_log, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::utils::utils::{thread_rng, HashMap, HashSet};
 
-    const EVICITON_RATE: f32 = 0.05;
+    // Each room comfortably sleeps this many people
+    const ROOM_CAPACITY: usize = 2;
+    // Annual chance a household whose home no longer suits it looks for another
+    const MOVE_CHANCE: f32 = 0.5;
+    // Annual chance a household with rooms to spare looks for somewhere smaller
+    const DOWNSIZE_CHANCE: f32 = 0.2;
+    // Annual chance a household living away from work looks for somewhere closer
+    const COMMUTE_MOVE_CHANCE: f32 = 0.1;
+    const DESIRABILITY_WEIGHT: i32 = 2;
+    const WORKPLACE_WEIGHT: i32 = 3;
+    const FAMILY_WEIGHT: i32 = 2;
+    const SPARE_ROOM_PENALTY: i32 = 1;
+    const CRAMPED_ROOM_PENALTY: i32 = 4;
+    pub const NEIGHBOUR_VERBS: [RelationVerb; 2] =
+        [RelationVerb::Neighbour, RelationVerb::BuildingNeighbour];
+    const FAMILY_VERBS: [RelationVerb; 7] = [
+        RelationVerb::Parent,
+        RelationVerb::Child,
+        RelationVerb::AdoptedParent,
+        RelationVerb::AdoptedChild,
+        RelationVerb::Sibling,
+        RelationVerb::Grandparent,
+        RelationVerb::Grandchild,
+    ];
 
-    pub fn random_evictions<'a>(city: &'a mut City) -> &'a mut City {
-        let mut rng = rand::thread_rng();
-        let r = city.citizens.clone();
-        let ref_pop: Vec<&Mind> = r
-            .values()
-            .filter(|c| c.alive && c.residence.is_some())
-            .collect();
-        let random_eviction_apartments: Vec<Uuid> = city
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Dwelling {
+        pub area_id: Uuid,
+        pub name: String,
+        pub building_name: String,
+        pub location_id: Uuid,
+        pub rooms: u32,
+        pub desirability: u32,
+        pub rent: i32,
+        pub landlord: Option<Landlord>,
+    }
+
+    // Snapshot of how well the city houses its households in a given year
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct HousingCensus {
+        pub year: usize,
+        pub homeless_households: usize,
+        pub homeless_citizens: usize,
+        pub overcrowded_households: usize,
+        pub overcrowded_citizens: usize,
+        pub vacant_homes: usize,
+    }
+
+    // What a household wants from a home
+    struct HousingNeeds {
+        rooms: u32,
+        // What the household can put towards rent this year
+        means: i32,
+        members: Vec<Uuid>,
+        workplaces: HashSet<Uuid>,
+        family: HashSet<Uuid>,
+    }
+
+    // Owner-occupiers live rent free
+    fn housing_cost(dwelling: &Dwelling, members: &Vec<Uuid>) -> i32 {
+        return match &dwelling.landlord {
+            Some(Landlord::Citizen(owner)) if members.contains(owner) => 0,
+            _ => dwelling.rent,
+        };
+    }
+
+    pub fn rooms_needed(household: &Household) -> u32 {
+        return household.members.len().div_ceil(ROOM_CAPACITY).max(1) as u32;
+    }
+
+    // Every apartment not taken over by an institution
+    pub fn find_dwellings(city: &City) -> HashMap<Uuid, Dwelling> {
+        return city
             .buildings
             .values()
             .flat_map(|b| {
-                b.floors
-                    .iter()
-                    .flat_map(|f| f.areas.iter().map(|a| a.id.clone()))
+                let location = city.areas.get(&b.location_id.unwrap()).unwrap();
+                b.floors.iter().flat_map(move |f| {
+                    f.areas
+                        .iter()
+                        .filter(|a| {
+                            a.area_type.eq(&FloorAreaType::Apartment)
+                                && a.owning_institution.is_none()
+                        })
+                        .map(move |a| {
+                            (
+                                a.id.clone(),
+                                Dwelling {
+                                    area_id: a.id.clone(),
+                                    name: a.name.clone(),
+                                    building_name: b.name.clone(),
+                                    location_id: location.id.clone(),
+                                    rooms: a.rooms,
+                                    desirability: location.desirability,
+                                    rent: a.rent,
+                                    landlord: a.landlord.clone(),
+                                },
+                            )
+                        })
+                })
             })
-            .filter(|_a| rng.gen::<f32>() < EVICITON_RATE)
             .collect();
-        for id in random_eviction_apartments {
-            for m in &ref_pop {
-                if m.residence.is_some() && m.residence.unwrap().eq(&id) {
-                    let m_mut = city.citizens.get_mut(&m.id).unwrap();
-                    m_mut.residence = None;
-
-                    drop(m_mut);
-                }
-            }
-        }
-        for m in ref_pop {
-            if m.age == 18 {
-                let mind = city.citizens.get_mut(&m.id).unwrap();
-                mind.residence = None;
-                drop(mind);
-            }
-        }
-        return city;
     }
 
-    pub fn assign_residences<'a>(city: &'a mut City) -> &'a mut City {
-        let ref_pop = city.citizens.clone();
-        let mut owned_ids: Vec<Uuid> = city
-            .citizens
+    fn area_locations(city: &City) -> HashMap<Uuid, Uuid> {
+        return city
+            .buildings
             .values()
-            .filter(|c| c.residence.is_some())
-            .map(|c| c.residence.unwrap().clone())
+            .flat_map(|b| {
+                b.floors.iter().flat_map(move |f| {
+                    f.areas
+                        .iter()
+                        .map(move |a| (a.id.clone(), b.location_id.unwrap().clone()))
+                })
+            })
             .collect();
+    }
 
-        let mut all_areas: Vec<(&BuildingFloorArea, String, Uuid)> = city
+    fn institution_locations(city: &City) -> HashMap<Uuid, Uuid> {
+        return city
             .buildings
             .values()
             .flat_map(|b| {
-                b.floors.iter().flat_map(|f| {
+                b.floors.iter().flat_map(move |f| {
                     f.areas
                         .iter()
-                        .map(|a| (a, b.name.clone(), b.location_id.unwrap().clone()))
+                        .filter(|a| a.owning_institution.is_some())
+                        .map(move |a| {
+                            (
+                                a.owning_institution.unwrap().clone(),
+                                b.location_id.unwrap().clone(),
+                            )
+                        })
                 })
             })
             .collect();
-        all_areas.shuffle(&mut rand::thread_rng());
+    }
+
+    fn housing_needs(
+        household: &Household,
+        city: &City,
+        areas: &HashMap<Uuid, Uuid>,
+        institutions: &HashMap<Uuid, Uuid>,
+    ) -> HousingNeeds {
+        let members: Vec<_> = household
+            .members
+            .iter()
+            .map(|id| city.citizens.get(id).unwrap())
+            .collect();
+        let workplaces: HashSet<Uuid> = members
+            .iter()
+            .filter_map(|m| m.employer.map(|e| institutions.get(&e)).flatten())
+            .cloned()
+            .collect();
+        let family: HashSet<Uuid> = members
+            .iter()
+            .flat_map(|m| m.relations.iter())
+            .filter(|(v, id)| FAMILY_VERBS.contains(v) && !household.members.contains(id))
+            .filter_map(|(_v, id)| city.citizens.get(id))
+            .filter(|m| m.alive && m.residence.is_some())
+            .filter_map(|m| areas.get(&m.residence.unwrap()))
+            .cloned()
+            .collect();
+        return HousingNeeds {
+            rooms: rooms_needed(household),
+            means: household_surplus(household, city) + household.budget,
+            members: household.members.clone(),
+            workplaces,
+            family,
+        };
+    }
+
+    fn score_dwelling(dwelling: &Dwelling, needs: &HousingNeeds) -> i32 {
+        let size_penalty = if dwelling.rooms < needs.rooms {
+            (needs.rooms - dwelling.rooms) as i32 * CRAMPED_ROOM_PENALTY
+        } else {
+            (dwelling.rooms - needs.rooms) as i32 * SPARE_ROOM_PENALTY
+        };
+        let mut score = dwelling.desirability as i32 * DESIRABILITY_WEIGHT - size_penalty;
+        if needs.workplaces.contains(&dwelling.location_id) {
+            score += WORKPLACE_WEIGHT;
+        }
+        if needs.family.contains(&dwelling.location_id) {
+            score += FAMILY_WEIGHT;
+        }
+        return score;
+    }
+
+    fn best_affordable<'a>(free: &'a Vec<Dwelling>, needs: &HousingNeeds) -> Option<&'a Dwelling> {
+        return free
+            .iter()
+            .filter(|d| housing_cost(d, &needs.members) <= needs.means)
+            .max_by_key(|d| score_dwelling(d, needs));
+    }
 
-        for citizen in ref_pop
+    fn occupied_areas(city: &City) -> HashSet<Uuid> {
+        return city
+            .citizens
             .values()
-            .filter(|c| c.alive && c.residence.is_none())
-        {
-            let mut target_res_id: Option<Uuid> = None;
-            let guardian = if citizen.age < ADULT_AGE_FROM {
-                find_relation(&citizen, RelationVerb::Parent, &ref_pop)
-            } else {
-                None
-            };
-            let guardian_res = if guardian.is_some() && guardian.unwrap().alive {
-                guardian.unwrap().residence
-            } else {
-                None
-            };
-            // let ward = find_relation_minor(&citizen, RelationVerb::Child, &ref_pop);
-            // let ward_res: Option<Uuid> = if ward.is_some() && ward.unwrap().alive {
-            //     ward.unwrap().residence
-            // } else {
-            //     None
-            // };
-            let spouse = find_relation(&citizen, RelationVerb::Spouse, &ref_pop);
-            let spouse_res: Option<Uuid> = if spouse.is_some() && spouse.unwrap().alive {
-                spouse.unwrap().residence
+            .filter(|c| c.alive && c.residence.is_some())
+            .map(|c| c.residence.unwrap().clone())
+            .collect();
+    }
+
+    fn move_in(city: &mut City, household_id: &Uuid, dwelling: &Dwelling) {
+        set_dwelling(city, household_id, Some(dwelling.area_id.clone()));
+        let location = city.areas.get(&dwelling.location_id).unwrap().name.clone();
+        let members = city.households.get(household_id).unwrap().members.clone();
+        let year = city.year;
+        for id in members {
+            let mind = city.citizens.get_mut(&id).unwrap();
+            add_residence_to_mind_log(
+                mind,
+                year,
+                &dwelling.name,
+                &dwelling.building_name,
+                &location,
+            );
+        }
+    }
+
+    // Households whose home no longer fits their size, means or work look for a better one
+    pub fn relocate_households<'a>(city: &'a mut City) -> &'a mut City {
+        let mut rng = thread_rng();
+        let dwellings = find_dwellings(city);
+        let areas = area_locations(city);
+        let institutions = institution_locations(city);
+        let occupied = occupied_areas(city);
+        let mut free: Vec<Dwelling> = dwellings
+            .values()
+            .filter(|d| !occupied.contains(&d.area_id))
+            .cloned()
+            .collect();
+        let housed: Vec<Uuid> = city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_some_and(|d| dwellings.contains_key(&d)))
+            .map(|h| h.id.clone())
+            .collect();
+        for household_id in housed {
+            let household = city.households.get(&household_id).unwrap();
+            let current = dwellings.get(&household.dwelling.unwrap()).unwrap();
+            let needs = housing_needs(household, city, &areas, &institutions);
+            let move_chance = if current.rooms < needs.rooms
+                || housing_cost(current, &needs.members) > needs.means
+            {
+                MOVE_CHANCE
+            } else if current.rooms > needs.rooms + 1 {
+                DOWNSIZE_CHANCE
+            } else if needs.workplaces.len() > 0 && !needs.workplaces.contains(&current.location_id)
+            {
+                COMMUTE_MOVE_CHANCE
             } else {
-                None
+                0.0
             };
-            // TODO - Currently broken, output looks very wrong
-            if guardian_res.is_some() {
-                target_res_id = guardian_res.clone();
-            } else if spouse_res.is_some() {
-                target_res_id = spouse_res.clone();
+            if rng.gen::<f32>() >= move_chance {
+                continue;
+            }
+            let candidate = best_affordable(&free, &needs);
+            if candidate.is_none()
+                || score_dwelling(candidate.unwrap(), &needs) <= score_dwelling(current, &needs)
+            {
+                continue;
+            }
+            let candidate = candidate.unwrap().clone();
+            free.retain(|d| !d.area_id.eq(&candidate.area_id));
+            free.push(current.clone());
+            move_in(city, &household_id, &candidate);
+        }
+        return city;
+    }
+
+    // Households without a home take the best one they can afford
+    pub fn assign_residences<'a>(city: &'a mut City) -> &'a mut City {
+        let dwellings = find_dwellings(city);
+        let areas = area_locations(city);
+        let institutions = institution_locations(city);
+        let occupied = occupied_areas(city);
+        let mut free: Vec<Dwelling> = dwellings
+            .values()
+            .filter(|d| !occupied.contains(&d.area_id))
+            .cloned()
+            .collect();
+        let homeless: Vec<Uuid> = city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_none())
+            .map(|h| h.id.clone())
+            .collect();
+        for household_id in homeless {
+            let household = city.households.get(&household_id).unwrap();
+            let needs = housing_needs(household, city, &areas, &institutions);
+            let choice = best_affordable(&free, &needs).cloned();
+            if choice.is_none() {
+                continue;
             }
+            let choice = choice.unwrap();
+            free.retain(|d| !d.area_id.eq(&choice.area_id));
+            move_in(city, &household_id, &choice);
+        }
+        link_neighbours(city);
+        return city;
+    }
+
+    pub fn is_overcrowded(household: &Household, dwellings: &HashMap<Uuid, Dwelling>) -> bool {
+        return household
+            .dwelling
+            .map(|d| dwellings.get(&d))
+            .flatten()
+            .is_some_and(|d| d.rooms < rooms_needed(household));
+    }
+
+    pub fn count_vacant_homes(city: &City) -> usize {
+        let occupied = occupied_areas(city);
+        return find_dwellings(city)
+            .keys()
+            .filter(|id| !occupied.contains(id))
+            .count();
+    }
+
+    // Households still in need of a home, or of a bigger one
+    pub fn housing_demand(city: &City) -> usize {
+        let dwellings = find_dwellings(city);
+        return city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_none() || is_overcrowded(h, &dwellings))
+            .count();
+    }
+
+    pub fn take_housing_census(city: &City) -> HousingCensus {
+        let dwellings = find_dwellings(city);
+        let homeless: Vec<&Household> = city
+            .households
+            .values()
+            .filter(|h| h.dwelling.is_none())
+            .collect();
+        let overcrowded: Vec<&Household> = city
+            .households
+            .values()
+            .filter(|h| is_overcrowded(h, &dwellings))
+            .collect();
+        return HousingCensus {
+            year: city.year,
+            homeless_households: homeless.len(),
+            homeless_citizens: homeless.iter().map(|h| h.members.len()).sum(),
+            overcrowded_households: overcrowded.len(),
+            overcrowded_citizens: overcrowded.iter().map(|h| h.members.len()).sum(),
+            vacant_homes: count_vacant_homes(city),
+        };
+    }
 
-            let apartment = all_areas.iter().find(|a| {
-                (target_res_id.is_some() && a.0.id.eq(&target_res_id.unwrap()))
-                    || (target_res_id.is_none()
-                        && a.0.owning_institution.is_none()
-                        && !owned_ids.contains(&a.0.id))
+    pub fn record_housing_census<'a>(city: &'a mut City) -> &'a mut City {
+        let census = take_housing_census(city);
+        city.housing_censuses.push(census);
+        return city;
+    }
+
+    pub fn print_housing_censuses(city: &City) -> String {
+        let mut output = String::new();
+        let current = take_housing_census(city);
+        output.push_str(&format!(
+            "Homeless: {} households ({} citizens)\n",
+            current.homeless_households, current.homeless_citizens
+        ));
+        output.push_str(&format!(
+            "Overcrowded: {} households ({} citizens)\n",
+            current.overcrowded_households, current.overcrowded_citizens
+        ));
+        output.push_str(&format!("Vacant Homes: {}\n", current.vacant_homes));
+        output.push_str("Housing By Year:\n");
+        for census in city.housing_censuses.iter() {
+            output.push_str(&format!(
+                "  Year {}: {} homeless ({} citizens), {} overcrowded ({} citizens), {} vacant homes\n",
+                census.year,
+                census.homeless_households,
+                census.homeless_citizens,
+                census.overcrowded_households,
+                census.overcrowded_citizens,
+                census.vacant_homes
+            ));
+        }
+        return output;
+    }
+
+    // The citizen's housing trouble, if they have one
+    pub fn describe_housing_need(mind: &Mind, city: &City) -> Option<String> {
+        let household = find_household(mind, city);
+        if mind.residence.is_none() {
+            return Some(match household.map(|h| h.homeless_since).flatten() {
+                Some(year) => format!("Homeless since year {}", year),
+                None => String::from("Homeless"),
             });
+        }
+        if household.is_some_and(|h| is_overcrowded(h, &find_dwellings(city))) {
+            return Some(String::from("Overcrowded"));
+        }
+        return None;
+    }
 
-            if apartment.is_some() {
-                let (area, building_name, location_id) = apartment.unwrap();
-                let location = city.areas.get(location_id);
-                owned_ids.push(area.id.clone());
-                let mind = city.citizens.get_mut(&citizen.id).unwrap();
-                mind.residence = Some(area.id.clone());
-                add_residence_to_mind_log(
-                    mind,
-                    city.year,
-                    &area.name,
-                    building_name,
-                    &location.unwrap().name,
-                );
-                drop(mind);
+    // Citizens in different homes on the same floor know each other as neighbours,
+    // and those elsewhere in the building as building neighbours
+    pub fn link_neighbours<'a>(city: &'a mut City) -> &'a mut City {
+        for mind in city.citizens.values_mut() {
+            mind.relations
+                .retain(|(v, _id)| !NEIGHBOUR_VERBS.contains(v));
+        }
+        let area_floors: HashMap<Uuid, (Uuid, Uuid)> = city
+            .buildings
+            .values()
+            .flat_map(|b| b.floors.iter().map(move |f| (b.id.clone(), f)))
+            .flat_map(|(building_id, f)| {
+                f.areas
+                    .iter()
+                    .filter(|a| a.owning_institution.is_none())
+                    .map(move |a| (a.id.clone(), (building_id.clone(), f.id.clone())))
+            })
+            .collect();
+        let mut buildings: HashMap<Uuid, Vec<(Uuid, Uuid, Uuid)>> = HashMap::default();
+        for mind in city
+            .citizens
+            .values()
+            .filter(|m| m.alive && m.residence.is_some())
+        {
+            let residence = mind.residence.unwrap();
+            let location = area_floors.get(&residence);
+            if location.is_some() {
+                let (building_id, floor_id) = location.unwrap();
+                buildings
+                    .entry(building_id.clone())
+                    .or_insert(Vec::new())
+                    .push((mind.id.clone(), floor_id.clone(), residence));
+            }
+        }
+        for residents in buildings.values() {
+            for (id, floor, residence) in residents.iter() {
+                let neighbours: Vec<(RelationVerb, Uuid)> = residents
+                    .iter()
+                    .filter(|(_other, _f, other_residence)| !other_residence.eq(residence))
+                    .map(|(other, other_floor, _r)| {
+                        let verb = if other_floor.eq(floor) {
+                            RelationVerb::Neighbour
+                        } else {
+                            RelationVerb::BuildingNeighbour
+                        };
+                        (verb, other.clone())
+                    })
+                    .collect();
+                city.citizens
+                    .get_mut(id)
+                    .unwrap()
+                    .relations
+                    .extend(neighbours);
             }
         }
         return city;
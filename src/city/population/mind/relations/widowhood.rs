@@ -0,0 +1,209 @@
+pub mod widowhood {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::household::household::{leave_household, set_dwelling};
+    use crate::city::population::mind::mind::{add_romatic_event_to_mind_log, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+    use crate::culture::culture::CultureConfig;
+    use crate::utils::utils::thread_rng;
+
+    // Past this age the odds of finding love again fall away each year
+    const REMARRIAGE_DECLINE_AGE: u32 = 40;
+    const REMARRIAGE_DECLINE_RATE: f32 = 0.02;
+    const MIN_REMARRIAGE_CHANCE: f32 = 0.05;
+
+    fn late_verb(verb: &RelationVerb) -> Option<RelationVerb> {
+        return match verb {
+            RelationVerb::Partner => Some(RelationVerb::LatePartner),
+            RelationVerb::Spouse => Some(RelationVerb::LateSpouse),
+            _ => None,
+        };
+    }
+
+    pub fn is_in_mourning(mind: &Mind, city: &City) -> bool {
+        return mind
+            .bereaved_in
+            .is_some_and(|year| year + city.culture.mourning_years as usize > city.year);
+    }
+
+    fn remarriage_chance(mind: &Mind, culture: &CultureConfig) -> f32 {
+        let decline =
+            mind.age.saturating_sub(REMARRIAGE_DECLINE_AGE) as f32 * REMARRIAGE_DECLINE_RATE;
+        return (culture.remarriage_rate - decline).max(MIN_REMARRIAGE_CHANCE);
+    }
+
+    // Whether a citizen is looking for love this year; the bereaved wait out their mourning first
+    pub fn open_to_new_partner(mind: &Mind, city: &City) -> bool {
+        if mind.bereaved_in.is_none() {
+            return true;
+        }
+        return !is_in_mourning(mind, city) && mind.will_remarry;
+    }
+
+    // A surviving partner in the household takes it over. A partner who lived alone leaves their
+    // savings shared between the survivors, and their home to the first survivor without one.
+    fn settle_estate(city: &mut City, dead_id: &Uuid, survivor_ids: &Vec<Uuid>) {
+        let household_id = city.citizens.get(dead_id).unwrap().household;
+        if household_id.is_none() {
+            return;
+        }
+        let household = city.households.get(&household_id.unwrap()).unwrap().clone();
+        leave_household(city, dead_id);
+        let resident = survivor_ids
+            .iter()
+            .find(|id| household.members.contains(id));
+        if resident.is_some() {
+            city.households.get_mut(&household.id).unwrap().head = resident.unwrap().clone();
+            return;
+        }
+        if household.members.len() > 1 {
+            return;
+        }
+        let mut heir_households: Vec<Uuid> = Vec::new();
+        for id in survivor_ids {
+            let heir_household = city.citizens.get(id).unwrap().household;
+            if heir_household.is_some() && !heir_households.contains(&heir_household.unwrap()) {
+                heir_households.push(heir_household.unwrap());
+            }
+        }
+        if heir_households.len() < 1 {
+            return;
+        }
+        let share = household.budget.max(0) / heir_households.len() as i32;
+        let mut dwelling = household.dwelling;
+        for heir_household_id in heir_households {
+            let heir_household = city.households.get_mut(&heir_household_id).unwrap();
+            heir_household.budget += share;
+            if heir_household.dwelling.is_none() && dwelling.is_some() {
+                set_dwelling(city, &heir_household_id, dwelling.take());
+            }
+        }
+    }
+
+    // Partners of the dead become late partners and go into mourning
+    pub fn bereave_survivors<'a>(city: &'a mut City, dead_ids: &Vec<Uuid>) -> &'a mut City {
+        let mut bereaved: Vec<(Uuid, Uuid, RelationVerb)> = Vec::new();
+        for mind in city.citizens.values() {
+            for (verb, id) in mind.relations.iter() {
+                if dead_ids.contains(id) && late_verb(verb).is_some() {
+                    bereaved.push((mind.id.clone(), id.clone(), verb.clone()));
+                }
+            }
+        }
+        let mut rng = thread_rng();
+        let mut survivors: Vec<(Uuid, Vec<Uuid>)> = Vec::new();
+        for (survivor_id, dead_id, verb) in bereaved {
+            let dead = city.citizens.get(&dead_id).unwrap().clone();
+            let year = city.year;
+            // whether they will ever look for love again is settled once, at the loss
+            let chance = remarriage_chance(city.citizens.get(&survivor_id).unwrap(), &city.culture);
+            let survivor = city.citizens.get_mut(&survivor_id).unwrap();
+            survivor
+                .relations
+                .retain(|(v, id)| !(v.eq(&verb) && id.eq(&dead_id)));
+            survivor
+                .relations
+                .push((late_verb(&verb).unwrap(), dead_id.clone()));
+            if survivor.alive {
+                survivor.bereaved_in = Some(year);
+                survivor.will_remarry = rng.gen::<f32>() < chance;
+                add_romatic_event_to_mind_log(survivor, year, verb, &dead, "Lost");
+                match survivors.iter_mut().find(|(id, _s)| id.eq(&dead_id)) {
+                    Some((_id, ids)) => ids.push(survivor_id),
+                    None => survivors.push((dead_id, vec![survivor_id])),
+                }
+            }
+        }
+        for (dead_id, survivor_ids) in survivors {
+            settle_estate(city, &dead_id, &survivor_ids);
+        }
+        return city;
+    }
+
+    #[test]
+    fn test_remarriage_chance_falls_with_age() {
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut culture = random_culture(&dict, &None);
+        culture.remarriage_rate = 0.6;
+        let mut mind = random_char(&dict, &culture, true);
+        let mut chances: Vec<f32> = Vec::new();
+        for age in [30, 40, 50, 60, 80, 100] {
+            mind.age = age;
+            chances.push(remarriage_chance(&mind, &culture));
+        }
+        assert_eq!(chances[0], culture.remarriage_rate);
+        assert_eq!(chances[1], culture.remarriage_rate);
+        assert!(chances.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(chances[3] < chances[2]);
+        assert_eq!(chances[5], MIN_REMARRIAGE_CHANCE);
+    }
+
+    #[test]
+    fn test_mourning_and_inheritance() {
+        use crate::city::city::new_city;
+        use crate::city::population::household::household::found_household;
+        use crate::city::population::mind::mind::random_char;
+        use crate::culture::culture::random_culture;
+        use crate::language::language::build_dictionary;
+
+        let dict = build_dictionary();
+        let mut city = new_city(String::new(), &random_culture(&dict, &None));
+        city.culture.mourning_years = 2;
+        city.culture.remarriage_rate = 1.0;
+        let mut ids: Vec<Uuid> = Vec::new();
+        for _i in 0..3 {
+            let mut mind = random_char(&dict, &city.culture, true);
+            mind.relations.clear();
+            mind.age = 35;
+            ids.push(mind.id.clone());
+            city.citizens.insert(mind.id.clone(), mind);
+        }
+        let (husband, first_wife, second_wife) = (ids[0], ids[1], ids[2]);
+        for wife in [first_wife, second_wife] {
+            for (id, other_id) in [(husband, wife), (wife, husband)] {
+                let mind = city.citizens.get_mut(&id).unwrap();
+                mind.relations.push((RelationVerb::Spouse, other_id));
+            }
+        }
+        // the husband lives apart from both wives, who keep a home each
+        let estate = found_household(&mut city, &husband);
+        city.households.get_mut(&estate).unwrap().budget = 100;
+        let dwelling = Some(Uuid::default());
+        set_dwelling(&mut city, &estate, dwelling);
+        let first_home = found_household(&mut city, &first_wife);
+        set_dwelling(&mut city, &first_home, Some(first_wife));
+        let second_home = found_household(&mut city, &second_wife);
+
+        city.citizens.get_mut(&husband).unwrap().alive = false;
+        bereave_survivors(&mut city, &vec![husband]);
+        for (wife, home) in [(first_wife, first_home), (second_wife, second_home)] {
+            let mind = city.citizens.get(&wife).unwrap();
+            assert!(mind
+                .relations
+                .contains(&(RelationVerb::LateSpouse, husband)));
+            assert!(is_in_mourning(mind, &city));
+            assert!(!open_to_new_partner(mind, &city));
+            assert_eq!(city.households.get(&home).unwrap().budget, 50);
+        }
+        // only the wife without a home of her own moves into his
+        assert_eq!(
+            city.households.get(&first_home).unwrap().dwelling,
+            Some(first_wife)
+        );
+        assert_eq!(
+            city.households.get(&second_home).unwrap().dwelling,
+            dwelling
+        );
+
+        city.year += 2;
+        let mind = city.citizens.get(&first_wife).unwrap();
+        assert!(!is_in_mourning(mind, &city));
+        assert!(open_to_new_partner(mind, &city));
+    }
+}
@@ -0,0 +1,64 @@
+pub mod retirement {
+    use uuid::Uuid;
+
+    use crate::city::city::City;
+    use crate::city::population::mind::appearance::appearance::{is_wealthy_employer, WealthTier};
+    use crate::city::population::mind::mind::{add_event_to_mind_log, find_employer, Mind};
+    use crate::city::population::mind::relations::relations::RelationVerb;
+
+    // Retirees without a pension live on whatever their working children can spare
+    fn family_support(mind: &Mind, city: &City) -> WealthTier {
+        let supported = mind.relations.iter().any(|(v, id)| {
+            (v.eq(&RelationVerb::Child) || v.eq(&RelationVerb::AdoptedChild))
+                && city
+                    .citizens
+                    .get(id)
+                    .is_some_and(|c| c.alive && c.employer.is_some())
+        });
+        return if supported {
+            WealthTier::Common
+        } else {
+            WealthTier::Poor
+        };
+    }
+
+    pub fn retiree_wealth_tier(mind: &Mind, city: &City) -> WealthTier {
+        return match mind.pension {
+            Some(tier) => tier,
+            None => family_support(mind, city),
+        };
+    }
+
+    pub fn retire_per_year<'a>(city: &'a mut City) -> &'a mut City {
+        let retiring: Vec<Uuid> = city
+            .citizens
+            .values()
+            .filter(|m| m.alive && !m.retired && m.age >= city.culture.retirement_age)
+            .map(|m| m.id)
+            .collect();
+        for id in retiring {
+            let mind = city.citizens.get(&id).unwrap();
+            let employer = find_employer(mind, city);
+            let employer_name = employer.map(|e| e.name.clone());
+            // a generous employer pays out well, public service and the state pay enough to get by
+            let pension = if employer.is_some() && is_wealthy_employer(employer.unwrap()) {
+                Some(WealthTier::Wealthy)
+            } else if city.culture.state_pension || employer.is_some_and(|e| e.public) {
+                Some(WealthTier::Common)
+            } else {
+                None
+            };
+            let year = city.year;
+            let mind = city.citizens.get_mut(&id).unwrap();
+            mind.retired = true;
+            mind.employer = None;
+            mind.pension = pension;
+            let event = match employer_name {
+                Some(name) => format!("retired from {}", name),
+                None => String::from("retired"),
+            };
+            add_event_to_mind_log(mind, year, &event);
+        }
+        return city;
+    }
+}
@@ -1,4 +1,8 @@
+pub mod fertility;
+pub mod marriage_customs;
+pub mod religion;
 pub mod culture {
+    use crate::city::population::mind::mind::{Gender, Sexuality};
     use crate::language::{
         language::*,
         nouns::{
@@ -8,7 +12,14 @@ pub mod culture {
         },
     };
     use rand::{seq::SliceRandom, Rng};
-    use uuid::Uuid;
+
+    use super::fertility::fertility::{era_fertility_profile, FertilityProfile};
+    use super::marriage_customs::marriage_customs::{
+        era_forbidden_kinship_distance, era_mourning_years, era_remarriage_rate,
+        random_marriage_customs, MarriageCustoms,
+    };
+    use super::religion::religion::{era_faithless_rate, random_faiths, Faith};
+    use crate::utils::utils::{random_uuid, thread_rng};
 
     #[derive(PartialEq, Debug, Clone)]
     pub struct CultureConfig {
@@ -18,12 +29,81 @@ pub mod culture {
         pub staple_meats: Vec<Word>,
         pub staple_plants: Vec<Word>,
         pub adult_age: u32,
+        pub retirement_age: u32,
+        pub state_pension: bool,
         pub species_avg_lifespan: u32,
         pub species_avg_lifespan_variance: u32,
         // Format (Man's last name, Woman's last name, Male Child's name, Female Child's name)
         pub parental_naming_formats: Vec<(String, String, String, String)>,
         pub avg_building_footprint: i32,
         pub avg_building_floors: i32,
+        pub gender_distribution: Vec<(Gender, f32)>,
+        pub sexuality_distribution: Vec<(Sexuality, f32)>,
+        pub faiths: Vec<Faith>,
+        pub faithless_rate: f32,
+        // Kin this close or closer (see kinship_distance) may not become partners, so 4 forbids up to first cousins
+        pub forbidden_kinship_distance: u32,
+        // Years the bereaved wait before looking for a new partner
+        pub mourning_years: u32,
+        // Chance the bereaved ever look for a new partner, settled once when they are widowed
+        pub remarriage_rate: f32,
+        pub marriage_customs: MarriageCustoms,
+        pub fertility: FertilityProfile,
+    }
+
+    pub fn era_gender_distribution(era: &Option<Era>) -> Vec<(Gender, f32)> {
+        let ambiguous_rate = match era {
+            Some(Era::Future) | Some(Era::Fantasy) => 0.2,
+            Some(Era::Medieval) => 0.04,
+            _ => 0.1,
+        };
+        return vec![
+            (Gender::Male, (1.0 - ambiguous_rate) / 2.0),
+            (Gender::Female, (1.0 - ambiguous_rate) / 2.0),
+            (Gender::Ambiguous, ambiguous_rate),
+        ];
+    }
+
+    pub fn era_sexuality_distribution(era: &Option<Era>) -> Vec<(Sexuality, f32)> {
+        let (homosexual_rate, bisexual_rate, asexual_rate) = match era {
+            Some(Era::Future) => (0.12, 0.15, 0.05),
+            Some(Era::Fantasy) => (0.1, 0.12, 0.04),
+            Some(Era::Medieval) => (0.05, 0.05, 0.02),
+            _ => (0.08, 0.1, 0.03),
+        };
+        return vec![
+            (
+                Sexuality::Hetrosexual,
+                1.0 - homosexual_rate - bisexual_rate - asexual_rate,
+            ),
+            (Sexuality::Homosexual, homosexual_rate),
+            (Sexuality::Bisexual, bisexual_rate),
+            (Sexuality::Asexual, asexual_rate),
+        ];
+    }
+
+    pub fn era_retirement_age(era: &Option<Era>) -> u32 {
+        return match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => 55,
+            Some(Era::Future) => 58,
+            _ => 60,
+        };
+    }
+
+    // Whether the culture provides for those too old to work, or leaves it to their families
+    pub fn era_has_state_pension(era: &Option<Era>) -> bool {
+        return match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => false,
+            _ => true,
+        };
+    }
+
+    fn vary_distribution<T: Clone>(input: Vec<(T, f32)>) -> Vec<(T, f32)> {
+        let mut rng = thread_rng();
+        return input
+            .iter()
+            .map(|(v, w)| (v.clone(), w * (0.75 + rng.gen::<f32>() * 0.5)))
+            .collect();
     }
 
     fn paternal_naming_lists() -> Vec<(String, String, String, String)> {
@@ -68,7 +148,7 @@ pub mod culture {
     }
 
     fn gen_historical_figures(dict: &Vec<Word>, era: &Option<Era>) -> Vec<(String, String)> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let figure_count = (rng.gen::<f32>() * 8.0) as usize;
 
         let mut output: Vec<(String, String)> = Vec::new();
@@ -100,7 +180,7 @@ pub mod culture {
     }
 
     fn random_animals(dict: &Vec<Word>, landlocked: bool, era: &Option<Era>) -> Vec<Word> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let len = (rng.gen::<f32>() * 5.0) as usize;
         let mut output: Vec<Word> = Vec::new();
         let mut animal_types = vec![CreatureFamily::CreatureFamilyMammal.to_string()];
@@ -129,7 +209,7 @@ pub mod culture {
     }
 
     fn random_crops(dict: &Vec<Word>, era: &Option<Era>) -> Vec<Word> {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let len = (rng.gen::<f32>() * 7.0) as usize;
         let mut output: Vec<Word> = Vec::new();
         for _i in 0..len.max(3) {
@@ -149,7 +229,7 @@ pub mod culture {
     }
 
     pub fn random_culture(dict: &Vec<Word>, era: &Option<Era>) -> CultureConfig {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let landlocked = rng.gen::<f32>() > 0.5;
         let naming_system_count = ((rng.gen::<f32>() * 3.0) as usize).max(1);
         let mut naming_systems = paternal_naming_lists();
@@ -171,6 +251,8 @@ pub mod culture {
             staple_meats: random_animals(&dict, landlocked, era),
             staple_plants: random_crops(&dict, era),
             adult_age: 18,
+            retirement_age: era_retirement_age(era),
+            state_pension: era_has_state_pension(era),
             species_avg_lifespan: 70,
             species_avg_lifespan_variance: 5,
             parental_naming_formats: naming_systems
@@ -180,6 +262,15 @@ pub mod culture {
                 .collect(),
             avg_building_footprint,
             avg_building_floors,
+            gender_distribution: vary_distribution(era_gender_distribution(era)),
+            sexuality_distribution: vary_distribution(era_sexuality_distribution(era)),
+            faiths: random_faiths(&dict, era),
+            faithless_rate: (era_faithless_rate(era) * (0.75 + rng.gen::<f32>() * 0.5)).min(1.0),
+            forbidden_kinship_distance: era_forbidden_kinship_distance(era),
+            mourning_years: era_mourning_years(era),
+            remarriage_rate: era_remarriage_rate(era),
+            marriage_customs: random_marriage_customs(era),
+            fertility: era_fertility_profile(era),
         };
     }
 
@@ -187,7 +278,7 @@ pub mod culture {
         let mut output = dict.clone();
         for (first_name, last_name) in culture.historical_figures.clone() {
             output.push(Word {
-                id: Uuid::new_v4(),
+                id: random_uuid(),
                 word_type: WordType::Noun,
                 text: first_name.clone(),
                 tags: vec![
@@ -197,7 +288,7 @@ pub mod culture {
                 related_forms: vec![],
             });
             output.push(Word {
-                id: Uuid::new_v4(),
+                id: random_uuid(),
                 word_type: WordType::Noun,
                 text: last_name.clone(),
                 tags: vec![
@@ -209,7 +300,7 @@ pub mod culture {
             // println!(
             //     "{:?}",
             //     Word {
-            //         id: Uuid::new_v4(),
+            //         id: random_uuid(),
             //         word_type: WordType::Noun,
             //         text: first_name.clone(),
             //         tags: vec![
@@ -222,7 +313,7 @@ pub mod culture {
             // println!(
             //     "{:?}",
             //     Word {
-            //         id: Uuid::new_v4(),
+            //         id: random_uuid(),
             //         word_type: WordType::Noun,
             //         text: last_name.clone(),
             //         tags: vec![
@@ -236,6 +327,25 @@ pub mod culture {
         return output;
     }
 
+    #[test]
+    fn test_era_distributions() {
+        let eras = [
+            None,
+            Some(Era::Medieval),
+            Some(Era::Modern),
+            Some(Era::Fantasy),
+            Some(Era::Future),
+        ];
+        for era in eras {
+            let genders = era_gender_distribution(&era);
+            let sexualities = era_sexuality_distribution(&era);
+            assert!(genders.iter().all(|(_g, w)| *w > 0.0));
+            assert!(sexualities.iter().all(|(_s, w)| *w > 0.0));
+            assert!((genders.iter().map(|(_g, w)| w).sum::<f32>() - 1.0).abs() < 0.001);
+            assert!((sexualities.iter().map(|(_s, w)| w).sum::<f32>() - 1.0).abs() < 0.001);
+        }
+    }
+
     #[test]
     fn test_random_culture() {
         let dict = build_dictionary();
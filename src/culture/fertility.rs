@@ -0,0 +1,134 @@
+pub mod fertility {
+    use crate::language::language::Era;
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct FertilityProfile {
+        // Ages between which a citizen can bear children, most readily at the peak
+        pub min_age: u32,
+        pub peak_age: u32,
+        pub end_age: u32,
+        // Annual chance a married couple has a child at the peak age
+        pub peak_rate: f32,
+        // Children the typical couple hopes for; each couple's own goal varies around this
+        pub desired_children: u32,
+        pub twin_rate: f32,
+        // Share of the couple rate that applies to those raising a child alone
+        pub single_parent_rate: f32,
+        // Annual chance a young child dies, high in eras without modern medicine
+        pub child_mortality: f32,
+    }
+
+    // Children younger than this are at risk from the era's child mortality
+    const CHILD_MORTALITY_AGE: u32 = 5;
+
+    pub fn era_fertility_profile(era: &Option<Era>) -> FertilityProfile {
+        return match era {
+            Some(Era::Medieval) => FertilityProfile {
+                min_age: 18,
+                peak_age: 24,
+                end_age: 44,
+                peak_rate: 0.35,
+                desired_children: 4,
+                twin_rate: 0.012,
+                single_parent_rate: 0.05,
+                child_mortality: 0.08,
+            },
+            Some(Era::Fantasy) => FertilityProfile {
+                min_age: 18,
+                peak_age: 26,
+                end_age: 48,
+                peak_rate: 0.32,
+                desired_children: 4,
+                twin_rate: 0.015,
+                single_parent_rate: 0.08,
+                child_mortality: 0.05,
+            },
+            Some(Era::Future) => FertilityProfile {
+                min_age: 20,
+                peak_age: 32,
+                end_age: 50,
+                peak_rate: 0.4,
+                desired_children: 3,
+                twin_rate: 0.02,
+                single_parent_rate: 0.15,
+                child_mortality: 0.001,
+            },
+            _ => FertilityProfile {
+                min_age: 18,
+                peak_age: 29,
+                end_age: 45,
+                peak_rate: 0.45,
+                desired_children: 3,
+                twin_rate: 0.016,
+                single_parent_rate: 0.12,
+                child_mortality: 0.002,
+            },
+        };
+    }
+
+    // Annual chance of a birth at the given age, rising to the peak and tailing off after it
+    pub fn fertility_at_age(age: u32, profile: &FertilityProfile) -> f32 {
+        if age < profile.min_age || age >= profile.end_age {
+            return 0.0;
+        }
+        if age <= profile.peak_age {
+            return profile.peak_rate
+                * ((age - profile.min_age + 1) as f32
+                    / (profile.peak_age - profile.min_age + 1) as f32);
+        }
+        return profile.peak_rate
+            * ((profile.end_age - age) as f32 / (profile.end_age - profile.peak_age) as f32);
+    }
+
+    pub fn child_death_chance(age: u32, profile: &FertilityProfile) -> f32 {
+        if age < CHILD_MORTALITY_AGE {
+            return profile.child_mortality;
+        }
+        return 0.0;
+    }
+
+    #[test]
+    fn test_fertility_curve() {
+        let profile = era_fertility_profile(&Some(Era::Modern));
+        assert_eq!(fertility_at_age(profile.min_age - 1, &profile), 0.0);
+        assert_eq!(fertility_at_age(profile.end_age, &profile), 0.0);
+        assert_eq!(
+            fertility_at_age(profile.peak_age, &profile),
+            profile.peak_rate
+        );
+        assert!(
+            fertility_at_age(profile.peak_age - 5, &profile)
+                < fertility_at_age(profile.peak_age, &profile)
+        );
+        assert!(
+            fertility_at_age(profile.peak_age + 5, &profile)
+                < fertility_at_age(profile.peak_age, &profile)
+        );
+    }
+
+    #[test]
+    fn test_child_mortality_allows_bounded_growth() {
+        let eras = [
+            None,
+            Some(Era::Medieval),
+            Some(Era::Modern),
+            Some(Era::Fantasy),
+            Some(Era::Future),
+        ];
+        for era in eras {
+            let profile = era_fertility_profile(&era);
+            for age in 0..CHILD_MORTALITY_AGE {
+                assert_eq!(child_death_chance(age, &profile), profile.child_mortality);
+            }
+            assert_eq!(child_death_chance(CHILD_MORTALITY_AGE, &profile), 0.0);
+            // a typical couple raises enough children to replace themselves, but never more than they hoped for
+            let survivors = profile.desired_children as f32
+                * (1.0 - profile.child_mortality).powi(CHILD_MORTALITY_AGE as i32);
+            assert!(survivors > 2.0, "{:?} raises {} children", era, survivors);
+            assert!(survivors <= profile.desired_children as f32);
+        }
+        let medieval = era_fertility_profile(&Some(Era::Medieval));
+        let modern = era_fertility_profile(&Some(Era::Modern));
+        assert!(child_death_chance(0, &medieval) > child_death_chance(0, &modern));
+    }
+}
@@ -0,0 +1,110 @@
+pub mod marriage_customs {
+    use rand::Rng;
+
+    use crate::language::language::Era;
+    use crate::utils::utils::{random_pick, thread_rng};
+
+    // Which family pays the other when a couple marries
+    #[derive(PartialEq, Debug, Clone)]
+    pub enum MarriagePayment {
+        None,
+        // paid by the bride's household to the groom's
+        Dowry,
+        // paid by the groom's household to the bride's
+        BridePrice,
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct MarriageCustoms {
+        pub min_marriage_age: u32,
+        // Annual chance an unmarried couple decides to marry
+        pub marriage_rate: f32,
+        // Annual chance parents arrange a match for a single grown child
+        pub arranged_marriage_rate: f32,
+        pub payment: MarriagePayment,
+        // Share of the paying household's budget handed over at the wedding
+        pub payment_share: f32,
+        pub same_sex_marriage: bool,
+        // Spouses a citizen may have at once, more than one in polygamous cultures
+        pub max_spouses: usize,
+    }
+
+    // Older eras tolerate matches between first cousins, later ones do not
+    pub fn era_forbidden_kinship_distance(era: &Option<Era>) -> u32 {
+        return match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => 3,
+            _ => 4,
+        };
+    }
+
+    pub fn era_mourning_years(era: &Option<Era>) -> u32 {
+        return match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => 1,
+            _ => 2,
+        };
+    }
+
+    // Older eras expect the bereaved to remarry, if only to keep the household going
+    pub fn era_remarriage_rate(era: &Option<Era>) -> f32 {
+        return match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => 0.7,
+            Some(Era::Future) => 0.5,
+            _ => 0.4,
+        };
+    }
+
+    pub fn random_marriage_customs(era: &Option<Era>) -> MarriageCustoms {
+        let mut rng = thread_rng();
+        let min_marriage_age = match era {
+            Some(Era::Future) => 21,
+            Some(Era::Fantasy) => 20,
+            _ => 18,
+        };
+        let marriage_rate = match era {
+            Some(Era::Medieval) => 0.25,
+            Some(Era::Fantasy) => 0.15,
+            Some(Era::Future) => 0.05,
+            _ => 0.075,
+        };
+        let arranged_marriage_rate = match era {
+            Some(Era::Medieval) => 0.3,
+            Some(Era::Fantasy) => 0.15,
+            _ => 0.0,
+        };
+        let payment = match era {
+            Some(Era::Medieval) => {
+                random_pick(&vec![MarriagePayment::Dowry, MarriagePayment::BridePrice])
+            }
+            Some(Era::Fantasy) => random_pick(&vec![
+                MarriagePayment::None,
+                MarriagePayment::Dowry,
+                MarriagePayment::BridePrice,
+            ]),
+            _ => MarriagePayment::None,
+        };
+        let same_sex_chance = match era {
+            Some(Era::Future) => 1.0,
+            Some(Era::Modern) => 0.8,
+            Some(Era::Fantasy) => 0.5,
+            Some(Era::Medieval) => 0.0,
+            None => 0.6,
+        };
+        let polygamy_chance = match era {
+            Some(Era::Medieval) | Some(Era::Fantasy) => 0.2,
+            _ => 0.05,
+        };
+        return MarriageCustoms {
+            min_marriage_age,
+            marriage_rate,
+            arranged_marriage_rate,
+            payment,
+            payment_share: 0.1 + rng.gen::<f32>() * 0.2,
+            same_sex_marriage: rng.gen::<f32>() < same_sex_chance,
+            max_spouses: if rng.gen::<f32>() < polygamy_chance {
+                2 + (rng.gen::<f32>() * 2.0) as usize
+            } else {
+                1
+            },
+        };
+    }
+}
@@ -0,0 +1,207 @@
+pub mod religion {
+    use rand::Rng;
+    use uuid::Uuid;
+
+    use crate::city::population::mind::mind::Gender;
+    use crate::language::language::*;
+    use crate::language::nouns::nouns::NounTag;
+    use crate::utils::utils::{random_pick, random_uuid, thread_rng};
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Deity {
+        pub id: Uuid,
+        pub name: String,
+        pub gender: Gender,
+        pub domain: String,
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    pub struct Faith {
+        pub id: Uuid,
+        pub name: String,
+        pub pantheon: Vec<Deity>,
+        // Noun used when naming this faith's places of worship, e.g. Temple
+        pub place_of_worship: String,
+        // Relative share of the faithful population following this faith
+        pub popularity: f32,
+        // Between 0 and 1, how often adherents attend services
+        pub devotion: f32,
+    }
+
+    fn random_deity(dict: &Vec<Word>, taken_names: &Vec<String>) -> Deity {
+        let mut rng = thread_rng();
+        let gender = random_pick(&vec![Gender::Male, Gender::Female, Gender::Ambiguous]);
+        let mut name_tags = vec![NounTag::DeityName.to_string()];
+        if !gender.eq(&Gender::Ambiguous) {
+            name_tags.push(format!("Gender{}", gender));
+        }
+        let mut name = random_word_by_tag_and(&dict, WordType::Noun, name_tags.clone())
+            .unwrap()
+            .text;
+        // fall back on old names once the list of divine ones is used up
+        for _i in 0..5 {
+            if !taken_names.contains(&name) {
+                break;
+            }
+            name = if rng.gen::<f32>() < 0.5 {
+                random_word_by_tag_and(&dict, WordType::Noun, name_tags.clone())
+            } else {
+                random_word_by_tag(
+                    &dict,
+                    WordType::Noun,
+                    &vec![NounTag::FirstName.to_string()],
+                    &vec![],
+                    &vec![],
+                    &Some(Era::Fantasy),
+                )
+            }
+            .unwrap()
+            .text;
+        }
+        let domain = random_word_by_tag_and(
+            &dict,
+            WordType::Noun,
+            vec![NounTag::DivineDomain.to_string()],
+        )
+        .unwrap()
+        .text;
+        return Deity {
+            id: random_uuid(),
+            name,
+            gender,
+            domain,
+        };
+    }
+
+    fn pantheon_size(era: &Option<Era>) -> usize {
+        let mut rng = thread_rng();
+        // older eras favour sprawling pantheons, later ones a single god
+        let max = match era {
+            Some(Era::Fantasy) => 8.0,
+            Some(Era::Medieval) => 4.0,
+            _ => 3.0,
+        };
+        let monotheism_chance = match era {
+            Some(Era::Fantasy) => 0.2,
+            Some(Era::Medieval) => 0.5,
+            _ => 0.6,
+        };
+        if rng.gen::<f32>() < monotheism_chance {
+            return 1;
+        }
+        return ((rng.gen::<f32>() * max) as usize).max(2);
+    }
+
+    pub fn random_faith(dict: &Vec<Word>, era: &Option<Era>, taken_names: &Vec<String>) -> Faith {
+        let mut rng = thread_rng();
+        let mut pantheon: Vec<Deity> = Vec::new();
+        for _i in 0..pantheon_size(era) {
+            let mut names: Vec<String> = pantheon.iter().map(|d| d.name.clone()).collect();
+            names.append(&mut taken_names.clone());
+            let deity = random_deity(&dict, &names);
+            if !pantheon.iter().any(|d| d.domain.eq(&deity.domain)) {
+                pantheon.push(deity);
+            }
+        }
+        let group = random_word_by_tag(
+            &dict,
+            WordType::Noun,
+            &vec![NounTag::FaithGroup.to_string()],
+            &vec![],
+            &vec![],
+            era,
+        )
+        .unwrap()
+        .text;
+        let name = if pantheon.len() == 1 {
+            format!("{} of {}", group, pantheon[0].name)
+        } else {
+            let patron = random_pick(&pantheon);
+            format!("{} of {}", group, patron.domain)
+        };
+        let place_of_worship = random_word_by_tag(
+            &dict,
+            WordType::Noun,
+            &vec![NounTag::PlaceOfWorship.to_string()],
+            &vec![],
+            &vec![],
+            era,
+        )
+        .unwrap()
+        .text;
+        return Faith {
+            id: random_uuid(),
+            name,
+            pantheon,
+            place_of_worship,
+            popularity: rng.gen::<f32>().max(0.1),
+            devotion: 0.2 + rng.gen::<f32>() * 0.8,
+        };
+    }
+
+    pub fn random_faiths(dict: &Vec<Word>, era: &Option<Era>) -> Vec<Faith> {
+        let mut rng = thread_rng();
+        let count = ((rng.gen::<f32>() * 4.0) as usize).max(1);
+        let mut output: Vec<Faith> = Vec::new();
+        for _i in 0..count {
+            let taken_names: Vec<String> = output
+                .iter()
+                .flat_map(|f| f.pantheon.iter().map(|d| d.name.clone()))
+                .collect();
+            output.push(random_faith(&dict, era, &taken_names));
+        }
+        return output;
+    }
+
+    // Share of each generation expected to grow up without any faith
+    pub fn era_faithless_rate(era: &Option<Era>) -> f32 {
+        return match era {
+            Some(Era::Future) => 0.5,
+            Some(Era::Modern) => 0.35,
+            Some(Era::Fantasy) => 0.1,
+            Some(Era::Medieval) => 0.05,
+            None => 0.25,
+        };
+    }
+
+    pub fn find_faith<'a>(faiths: &'a Vec<Faith>, id: &Uuid) -> Option<&'a Faith> {
+        return faiths.iter().find(|f| f.id.eq(id));
+    }
+
+    pub fn find_deity<'a>(faiths: &'a Vec<Faith>, id: &Uuid) -> Option<&'a Deity> {
+        return faiths
+            .iter()
+            .flat_map(|f| f.pantheon.iter())
+            .find(|d| d.id.eq(id));
+    }
+
+    pub fn describe_deity(deity: &Deity) -> String {
+        let role = match deity.gender {
+            Gender::Male => "God",
+            Gender::Female => "Goddess",
+            Gender::Ambiguous => "Deity",
+        };
+        return format!("{}, {} of {}", deity.name, role, deity.domain);
+    }
+
+    #[test]
+    fn test_random_faiths() {
+        let dict = build_dictionary();
+        for era in [
+            None,
+            Some(Era::Medieval),
+            Some(Era::Fantasy),
+            Some(Era::Future),
+        ] {
+            let faiths = random_faiths(&dict, &era);
+            assert!(faiths.len() > 0);
+            for faith in faiths {
+                assert!(faith.pantheon.len() > 0);
+                println!("{}", faith.name);
+                for deity in faith.pantheon {
+                    println!("  {}", describe_deity(&deity));
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@ pub mod language {
     use uuid::Uuid;
 
     use super::{adjectives::adjectives::build_adjectives, nouns::nouns::build_nouns};
+    use crate::utils::utils::thread_rng;
 
     #[derive(PartialEq, Debug, Clone, EnumIter, Display, Copy, Default)]
     pub enum Era {
@@ -60,7 +61,7 @@ pub mod language {
                 output.push(word.clone());
             }
         }
-        output.shuffle(&mut rand::thread_rng());
+        output.shuffle(&mut thread_rng());
         for word in output {
             return Some(word);
         }
@@ -104,7 +105,7 @@ pub mod language {
                 output.push(word.clone());
             }
         }
-        output.shuffle(&mut rand::thread_rng());
+        output.shuffle(&mut thread_rng());
         for word in output {
             return Some(word);
         }
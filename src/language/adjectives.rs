@@ -2,8 +2,8 @@ pub mod adjectives {
     use std::fs;
     use strum::IntoEnumIterator;
     use strum_macros::{Display, EnumIter};
-    use uuid::Uuid; // 0.17.1
 
+    use crate::utils::utils::random_uuid;
     use crate::{language::language::*, parser::parser::parse_file};
 
     #[derive(PartialEq, Debug, Clone, EnumIter, Display, Copy)]
@@ -17,6 +17,12 @@ pub mod adjectives {
         HairLength,
         CreatureHeight,
         CreatureBuild,
+        AgeingFeature,
+        SkinTone,
+        FaceShape,
+        DistinguishingMark,
+        Voice,
+        AttireStyle,
         Size,
         Taste,
         Positive,
@@ -49,7 +55,7 @@ pub mod adjectives {
                     }
                 }
                 output.push(Word {
-                    id: Uuid::new_v4(),
+                    id: random_uuid(),
                     word_type: WordType::Adjective,
                     text: subject,
                     tags,
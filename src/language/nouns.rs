@@ -12,13 +12,13 @@ pub mod nouns {
     use regex::Regex;
     use strum::IntoEnumIterator; // 0.17.1
     use strum_macros::{Display, EnumIter};
-    use uuid::Uuid;
 
     use super::{
         creatures::creatures::creature_tags, emotions::emotions::emotion_group_tags,
         era::eras::era_tags, food::food::food_tags, geography::geography::geography_tags,
         materials::materials::material_tags, plants::plants::plant_tags,
     };
+    use crate::utils::utils::random_uuid;
 
     #[derive(PartialEq, Debug, Clone, EnumIter, Display, Copy)]
     pub enum NounTag {
@@ -30,6 +30,9 @@ pub mod nouns {
         Good,
         Evil,
         Holy,
+        DivineDomain,
+        FaithGroup,
+        PlaceOfWorship,
         Institution,
         Affliction,
         Symbolic,
@@ -52,6 +55,9 @@ pub mod nouns {
         GlobalSingular,
         Direction,
         Title,
+        Nobility,
+        Honorific,
+        Academic,
         BuildingTitle,
         GeneralRetailerName,
         RetailerFood,
@@ -60,12 +66,14 @@ pub mod nouns {
         EntertainmentVenu,
         ServiceAdmin,
         Suffixable,
+        Plural,
         // Names / People
         FirstName,
         LastName,
         GenderMale,
         GenderFemale,
         HistoricalFigure,
+        DeityName,
         FoodProduct,
     }
 
@@ -138,7 +146,7 @@ pub mod nouns {
                 let adjectives: Vec<Word> = adjective_terms
                     .iter()
                     .map(|t| Word {
-                        id: Uuid::new_v4(),
+                        id: random_uuid(),
                         word_type: WordType::Adjective,
                         text: String::from(t),
                         tags: subject_tags.clone(),
@@ -146,7 +154,7 @@ pub mod nouns {
                     })
                     .collect();
                 output.push(Word {
-                    id: Uuid::new_v4(),
+                    id: random_uuid(),
                     word_type: WordType::Noun,
                     text: subject,
                     tags: subject_tags,
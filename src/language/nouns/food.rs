@@ -5,6 +5,7 @@ pub mod food {
     use strum_macros::{Display, EnumIter};
     use uuid::Uuid;
 
+    use crate::utils::utils::thread_rng;
     use crate::{
         culture::culture::*,
         language::{
@@ -104,7 +105,7 @@ pub mod food {
             CreatureCategory::CreatureMagical.to_string(),
             NounTag::FoodProduct.to_string(),
         ];
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut ingredients: Vec<&Word> = dict
             .iter()
             .filter(|w| {
@@ -133,7 +134,7 @@ pub mod food {
             ingredients.retain(|b: &&Word| staples_ids.contains(&b.id));
         }
 
-        ingredients.shuffle(&mut rand::thread_rng());
+        ingredients.shuffle(&mut thread_rng());
         return ingredients.first().unwrap().clone();
     }
 
@@ -149,7 +150,7 @@ pub mod food {
                 w.text.eq(&dish_type.text) && w.tags.contains(&NounTag::FoodProduct.to_string())
             })
             .collect();
-        dish_variations.shuffle(&mut rand::thread_rng());
+        dish_variations.shuffle(&mut thread_rng());
         let target_dish = dish_variations.first().unwrap();
 
         let ingredients: Vec<String> = target_dish
@@ -307,10 +308,10 @@ pub mod food {
                 dish_types_unique.push(word.text.clone());
             }
         }
-        dish_types_unique.shuffle(&mut rand::thread_rng());
+        dish_types_unique.shuffle(&mut thread_rng());
         let target_dish = dish_types_unique.first().unwrap().clone();
         dish_types_repeats.retain(|w| w.text.eq(&target_dish));
-        dish_types_repeats.shuffle(&mut rand::thread_rng());
+        dish_types_repeats.shuffle(&mut thread_rng());
         return dish_types_repeats.first().unwrap().clone();
     }
 
@@ -1,9 +1,84 @@
 pub mod utils {
+    use rand::rngs::StdRng;
     use rand::seq::SliceRandom;
+    use rand::{Rng, RngCore, SeedableRng};
+    use std::cell::RefCell;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+    use uuid::Uuid;
+
+    // Maps and sets that iterate in the same order on every run, so a seeded simulation repeats exactly
+    pub type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+    pub type HashSet<T> = std::collections::HashSet<T, BuildHasherDefault<DefaultHasher>>;
+
+    thread_local! {
+        static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+    }
+
+    // The source of every random choice in the simulation, seeded from entropy unless seed_rng is called
+    #[derive(Clone, Copy, Debug)]
+    pub struct SimulationRng;
+
+    impl RngCore for SimulationRng {
+        fn next_u32(&mut self) -> u32 {
+            return RNG.with(|rng| rng.borrow_mut().next_u32());
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            return RNG.with(|rng| rng.borrow_mut().next_u64());
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            RNG.with(|rng| rng.borrow_mut().fill_bytes(dest));
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            return RNG.with(|rng| rng.borrow_mut().try_fill_bytes(dest));
+        }
+    }
+
+    pub fn thread_rng() -> SimulationRng {
+        return SimulationRng;
+    }
+
+    pub fn seed_rng(seed: u64) {
+        RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+    }
+
+    pub fn random_uuid() -> Uuid {
+        return uuid::Builder::from_random_bytes(thread_rng().gen()).into_uuid();
+    }
+
     pub fn random_pick<T: Clone>(input: &Vec<T>) -> T {
-        let mut rng = rand::thread_rng();
+        let mut rng = thread_rng();
         let mut i = input.clone();
         i.shuffle(&mut rng);
         return i.pop().unwrap();
     }
+
+    pub fn random_weighted_pick<T: Clone>(input: &Vec<(T, f32)>) -> T {
+        let mut rng = thread_rng();
+        let total: f32 = input.iter().map(|(_v, w)| w).sum();
+        let mut roll = rng.gen::<f32>() * total;
+        for (value, weight) in input {
+            if roll < *weight {
+                return value.clone();
+            }
+            roll -= weight;
+        }
+        // rounding can leave a sliver of the roll over, which belongs to the last weighted entry
+        let last = input.iter().rev().find(|(_v, w)| *w > 0.0);
+        return last.unwrap_or(input.last().unwrap()).0.clone();
+    }
+
+    #[test]
+    fn test_random_weighted_pick() {
+        let single = vec![("only", 1.0)];
+        let weighted = vec![("never", 0.0), ("sometimes", 0.3), ("often", 0.7), ("not", 0.0)];
+        for _i in 0..1000 {
+            assert_eq!(random_weighted_pick(&single), "only");
+            let pick = random_weighted_pick(&weighted);
+            assert!(pick.ne("never") && pick.ne("not"));
+        }
+    }
 }
\ No newline at end of file